@@ -0,0 +1,125 @@
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Derive an expression of x.
+
+Usage: stoat [OPTIONS] [EXPR]
+
+Arguments:
+  [EXPR]  The expression to derive, read from stdin when missing or `-`.
+          Start an interactive session when missing and stdin is a terminal
+
+Options:
+  -f, --file <PATH>     Read the expression from a file
+  -n, --order <N>       Derive N times [default: 1]
+  -s, --simplify        Simplify the result
+  -e, --eval <X>        Evaluate the result at X, can be repeated or comma separated
+  -o, --output <FORMAT> Output format: plain, pretty, latex or json [default: plain]
+  -i, --interactive     Start an interactive session
+      --script          Run the input as a script of several lines, with `let` bindings
+                        and `#` comments, and print the result of every expression line
+  -h, --help            Print this help
+";
+
+#[derive(Debug, PartialEq)]
+pub enum Input {
+    Arg(String),
+    Stdin,
+    File(PathBuf),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Output {
+    Plain,
+    Pretty,
+    Latex,
+    Json,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub input: Input,
+    pub order: usize,
+    pub simplify: bool,
+    pub points: Vec<f64>,
+    pub output: Output,
+    pub interactive: bool,
+    pub script: bool,
+    pub help: bool,
+}
+
+impl Args {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Args {
+            input: Input::Stdin,
+            order: 1,
+            simplify: false,
+            points: vec![],
+            output: Output::Plain,
+            interactive: false,
+            script: false,
+            help: false,
+        };
+        let mut expr = None;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("missing value for `{name}`"))
+            };
+
+            match arg.as_str() {
+                "-h" | "--help" => parsed.help = true,
+                "-s" | "--simplify" => parsed.simplify = true,
+                "-i" | "--interactive" => parsed.interactive = true,
+                "--script" => parsed.script = true,
+                "-f" | "--file" => parsed.input = Input::File(value(&arg)?.into()),
+                "-n" | "--order" => {
+                    let n = value(&arg)?;
+                    parsed.order = n
+                        .parse()
+                        .map_err(|_| format!("invalid derivative order `{n}`"))?;
+                }
+                "-e" | "--eval" => {
+                    for x in value(&arg)?.split(',') {
+                        let point = x
+                            .trim()
+                            .parse()
+                            .map_err(|_| format!("invalid point `{x}`"))?;
+                        parsed.points.push(point);
+                    }
+                }
+                "-o" | "--output" => {
+                    parsed.output = match value(&arg)?.as_str() {
+                        "plain" => Output::Plain,
+                        "pretty" => Output::Pretty,
+                        "latex" => Output::Latex,
+                        "json" => Output::Json,
+                        f => return Err(format!("unknown output format `{f}`")),
+                    }
+                }
+                "-" => expr = Some(Input::Stdin),
+                a if a.starts_with('-') && a.len() > 1 && !is_number(a) => {
+                    return Err(format!("unknown option `{a}`"))
+                }
+                _ if expr.is_some() => return Err("more than one expression given".into()),
+                _ => expr = Some(Input::Arg(arg)),
+            }
+        }
+
+        if let Some(expr) = expr {
+            if matches!(parsed.input, Input::File(_)) {
+                return Err("both an expression and a file given".into());
+            }
+            parsed.input = expr;
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// A negative number like `-1` is an expression, not an option.
+fn is_number(arg: &str) -> bool {
+    arg[1..].starts_with(|c: char| c.is_ascii_digit())
+}
@@ -0,0 +1,209 @@
+use std::fmt::Display;
+use std::io::{IsTerminal, Read};
+use std::process::ExitCode;
+
+use stoat_prime::lang::Script;
+use stoat_prime::{Expr, Registry};
+
+use crate::args::{Args, Input, Output};
+
+mod args;
+mod repl;
+#[cfg(test)]
+mod test;
+
+/// Exit code for an expression that could not be parsed.
+const EXIT_LANG: u8 = 1;
+/// Exit code for bad arguments or input that could not be read.
+const EXIT_USAGE: u8 = 2;
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", args::USAGE);
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+
+    if args.help {
+        print!("{}", args::USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    if args.interactive || (args.input == Input::Stdin && std::io::stdin().is_terminal()) {
+        return match repl::run() {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("error: {e}");
+                ExitCode::from(EXIT_USAGE)
+            }
+        };
+    }
+
+    let source = match read_input(&args.input) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+    let source = source.trim();
+
+    if args.script {
+        return run_script(source, &args);
+    }
+
+    let expr = match Registry::standard().parse(source) {
+        Ok(expr) => expr,
+        Err(e) => {
+            eprint!("{}", diagnostic(source, &e, e.loc()));
+            return ExitCode::from(EXIT_LANG);
+        }
+    };
+
+    let mut result = expr;
+    for _ in 0..args.order {
+        result = result.derive();
+    }
+    if args.simplify {
+        result = result.simplify();
+    }
+
+    let values: Vec<_> = args
+        .points
+        .iter()
+        .map(|&x| (x, result.evaluate(x)))
+        .collect();
+
+    match args.output {
+        Output::Plain => println!("{result}"),
+        Output::Pretty => println!("{}", result.pretty()),
+        Output::Latex => println!("{}", result.latex()),
+        Output::Json => {
+            println!("{}", json(source, args.order, &result, &values));
+            return ExitCode::SUCCESS;
+        }
+    }
+
+    for (x, y) in values {
+        println!("at x = {x}: {y}");
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Run the source as a script and print every result.
+fn run_script(source: &str, args: &Args) -> ExitCode {
+    let outputs = match Script::with_functions(Registry::standard()).run(source) {
+        Ok(outputs) => outputs,
+        Err(e) => {
+            eprint!("{}", diagnostic(source, &e, e.loc()));
+            return ExitCode::from(EXIT_LANG);
+        }
+    };
+
+    let results = outputs.into_iter().map(|o| {
+        let expr = if args.simplify {
+            o.expr.simplify()
+        } else {
+            o.expr
+        };
+        (o.line, expr)
+    });
+
+    match args.output {
+        Output::Json => {
+            let results: Vec<_> = results
+                .map(|(line, expr)| {
+                    format!(
+                        "{{\"line\":{line},\"result\":{},\"latex\":{}}}",
+                        json_str(&expr.to_string()),
+                        json_str(&expr.latex().to_string())
+                    )
+                })
+                .collect();
+            println!("[{}]", results.join(","));
+        }
+        Output::Plain => results.for_each(|(_, expr)| println!("{expr}")),
+        Output::Pretty => results.for_each(|(_, expr)| println!("{}\n", expr.pretty())),
+        Output::Latex => results.for_each(|(_, expr)| println!("{}", expr.latex())),
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn read_input(input: &Input) -> std::io::Result<String> {
+    match input {
+        Input::Arg(s) => Ok(s.clone()),
+        Input::File(path) => std::fs::read_to_string(path),
+        Input::Stdin => {
+            let mut s = String::new();
+            std::io::stdin().read_to_string(&mut s)?;
+            Ok(s)
+        }
+    }
+}
+
+/// Print the error with the line of the source and a caret under where it happened, `loc` is a
+/// byte offset in the source.
+fn diagnostic(source: &str, error: &dyn Display, loc: Option<usize>) -> String {
+    let mut out = format!("error: {error}\n");
+
+    if let Some(loc) = loc {
+        let loc = loc.min(source.len());
+        let start = source[..loc].rfind('\n').map_or(0, |i| i + 1);
+        let end = source[loc..].find('\n').map_or(source.len(), |i| loc + i);
+        let line = source[..start].lines().count() + 1;
+
+        let number = line.to_string();
+        let col = source[start..loc].chars().count();
+        out += &format!(
+            "{number} | {}\n{} | {}^\n",
+            &source[start..end],
+            " ".repeat(number.len()),
+            " ".repeat(col)
+        );
+    }
+
+    out
+}
+
+fn json(source: &str, order: usize, result: &Expr, values: &[(f64, f64)]) -> String {
+    let values: Vec<_> = values
+        .iter()
+        .map(|&(x, y)| format!("{{\"x\":{},\"y\":{}}}", json_num(x), json_num(y)))
+        .collect();
+
+    format!(
+        "{{\"input\":{},\"order\":{order},\"result\":{},\"latex\":{},\"values\":[{}]}}",
+        json_str(source),
+        json_str(&result.to_string()),
+        json_str(&result.latex().to_string()),
+        values.join(",")
+    )
+}
+
+fn json_str(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            c if c.is_control() => out += &format!("\\u{:04x}", c as u32),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// JSON has no infinity or NaN.
+fn json_num(n: f64) -> String {
+    if n.is_finite() {
+        n.to_string()
+    } else {
+        "null".into()
+    }
+}
@@ -0,0 +1,60 @@
+use std::io::{BufRead, Write};
+
+use stoat_prime::repl::{Reply, Session};
+use stoat_prime::Registry;
+
+use crate::diagnostic;
+
+const HELP: &str = "\
+  f = x^2 sin x   bind an expression to a name
+  f'              derive f, add more primes for higher derivatives
+  f''(2)          evaluate the second derivative of f at 2
+  simplify f'     simplify a result
+  derive f        same as f'
+  ans             the last result, like ans * 2
+  :vars           list every binding
+  :history        list every line
+  :quit           leave
+";
+
+pub fn run() -> std::io::Result<()> {
+    let mut session = Session::with_functions(Registry::standard());
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+
+    loop {
+        write!(stdout, "> ")?;
+        stdout.flush()?;
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            writeln!(stdout)?;
+            return Ok(());
+        }
+        let line = line.trim_end_matches(['\n', '\r']);
+
+        match line.trim() {
+            "" => continue,
+            ":quit" | ":q" => return Ok(()),
+            ":help" | ":h" => print!("{HELP}"),
+            ":vars" => {
+                for (name, expr) in session.bindings() {
+                    println!("{name} = {expr}");
+                }
+            }
+            ":history" => {
+                for (i, line) in session.history().iter().enumerate() {
+                    println!("{:>4}  {line}", i + 1);
+                }
+            }
+            c if c.starts_with(':') => eprintln!("error: unknown command `{c}`, try :help"),
+
+            _ => match session.eval(line) {
+                Ok(Reply::Expr(expr)) => println!("{expr}"),
+                Ok(Reply::Bound(name, expr)) => println!("{name} = {expr}"),
+                Ok(Reply::Value(v)) => println!("{v}"),
+                Err(e) => eprint!("{}", diagnostic(line, &e, e.loc())),
+            },
+        }
+    }
+}
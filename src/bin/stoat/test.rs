@@ -0,0 +1,89 @@
+use crate::args::{Args, Input, Output};
+use crate::diagnostic;
+
+fn parse(args: &[&str]) -> Result<Args, String> {
+    Args::parse(args.iter().map(|a| a.to_string()))
+}
+
+#[test]
+fn defaults() {
+    let args = parse(&[]).unwrap();
+    assert_eq!(args.input, Input::Stdin);
+    assert_eq!(args.order, 1);
+    assert_eq!(args.output, Output::Plain);
+    assert!(!args.simplify && !args.interactive && !args.script && !args.help);
+}
+
+#[test]
+fn options() {
+    let args = parse(&[
+        "-n", "2", "-s", "-e", "1, 2", "-e", "3", "-o", "json", "x^2",
+    ])
+    .unwrap();
+    assert_eq!(args.input, Input::Arg("x^2".into()));
+    assert_eq!(args.order, 2);
+    assert!(args.simplify);
+    assert_eq!(args.points, [1.0, 2.0, 3.0]);
+    assert_eq!(args.output, Output::Json);
+
+    let args = parse(&["--file", "f.txt", "--script"]).unwrap();
+    assert_eq!(args.input, Input::File("f.txt".into()));
+    assert!(args.script);
+}
+
+#[test]
+fn negative_expr() {
+    assert_eq!(
+        parse(&["-1 * x"]).unwrap().input,
+        Input::Arg("-1 * x".into())
+    );
+    assert_eq!(parse(&["-"]).unwrap().input, Input::Stdin);
+}
+
+mod error {
+    use super::*;
+
+    #[test]
+    fn missing_value() {
+        assert_eq!(parse(&["x", "-n"]).unwrap_err(), "missing value for `-n`");
+    }
+
+    #[test]
+    fn invalid_values() {
+        assert_eq!(
+            parse(&["-n", "two"]).unwrap_err(),
+            "invalid derivative order `two`"
+        );
+        assert_eq!(parse(&["-e", "1,a"]).unwrap_err(), "invalid point `a`");
+        assert_eq!(
+            parse(&["-o", "html"]).unwrap_err(),
+            "unknown output format `html`"
+        );
+    }
+
+    #[test]
+    fn unknown_option() {
+        assert_eq!(parse(&["--fast"]).unwrap_err(), "unknown option `--fast`");
+    }
+
+    #[test]
+    fn two_inputs() {
+        assert_eq!(
+            parse(&["x", "x^2"]).unwrap_err(),
+            "more than one expression given"
+        );
+        assert_eq!(
+            parse(&["-f", "f.txt", "x"]).unwrap_err(),
+            "both an expression and a file given"
+        );
+    }
+}
+
+#[test]
+fn caret() {
+    assert_eq!(
+        diagnostic("let a = x\na + )", &"unexpected token", Some(14)),
+        "error: unexpected token\n2 | a + )\n  |     ^\n"
+    );
+    assert_eq!(diagnostic("x", &"oops", None), "error: oops\n");
+}
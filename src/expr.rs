@@ -1,8 +1,42 @@
 use std::f64::consts::E;
+use std::sync::Arc;
 
+mod analysis;
+mod codegen;
 mod combinator;
 mod derive;
 mod display;
+mod dual;
+mod expand;
+mod format;
+mod function;
+mod integrate;
+mod latex;
+mod limit;
+mod logs;
+mod polynomial;
+mod pretty;
+mod quadrature;
+mod roots;
+mod simplify;
+mod substitute;
+mod tape;
+mod taylor;
+mod trig;
+
+pub use analysis::{Analysis, Concavity, CriticalPoint, Monotonicity, PointKind};
+pub use display::Formatted;
+pub use dual::{Dual, Scalar};
+pub use format::{Notation, NumFormat};
+pub use function::{Function, Registry};
+pub use latex::Latex;
+pub use limit::{Direction, Limit};
+pub use logs::Domain;
+pub use polynomial::{NotPolynomial, Polynomial};
+pub use pretty::Pretty;
+pub use quadrature::Integral;
+pub use roots::{Complex, Root};
+pub use tape::Tape;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Trig {
@@ -41,6 +75,16 @@ impl Bin {
 pub enum Expr {
     Bin(Bin, Box<Expr>, Box<Expr>),
     Trig(Trig, Box<Expr>),
+    /// The nth derivative of an expr, kept as is until evaluated or expanded.
+    Derivative(usize, Box<Expr>),
+    /// A user defined function applied to an expr.
+    Func(Arc<Function>, Box<Expr>),
+    /// An unknown function like `f''(x)`, with its name, the order of its derivative and its
+    /// argument.
+    Unknown(String, usize, Box<Expr>),
+    /// A named value other than `x`, like `$a` in `$a * x^2`. It does not depend on `x`, so its
+    /// derivative is 0, but it is not constant as it only has a value on a [`Tape`].
+    Param(String),
     Var,
     Num(f64),
 }
@@ -60,45 +104,29 @@ impl Expr {
         matches!(self, Self::Var)
     }
 
-    pub fn precedence(&self) -> usize {
+    /// Whether the expr has a value without `x`, so it does not depend on `x`, a param or an
+    /// unknown function.
+    pub fn is_constant(&self) -> bool {
         match self {
-            Expr::Bin(t, ..) => t.precedence(),
-            Expr::Trig(..) => 3,
-            Expr::Var | Expr::Num(_) => 100,
+            Expr::Bin(_, a, b) => a.is_constant() && b.is_constant(),
+            Expr::Trig(_, a) | Expr::Derivative(_, a) | Expr::Func(_, a) => a.is_constant(),
+            // the value is unknown even at a constant
+            Expr::Unknown(..) | Expr::Param(_) | Expr::Var => false,
+            Expr::Num(_) => true,
         }
     }
 
-    pub fn evaluate(self, value: f64) -> f64 {
+    pub fn precedence(&self) -> usize {
         match self {
-            Expr::Bin(bin, a, b) => {
-                let a = a.evaluate(value);
-                let b = b.evaluate(value);
-
-                match bin {
-                    Bin::Add => a + b,
-                    Bin::Sub => a - b,
-                    Bin::Div => a / b,
-                    Bin::Mul => a * b,
-                    Bin::Exp => a.powf(b),
-                    Bin::Log => a.log(b),
-                }
-            }
-            Expr::Trig(trig, v) => {
-                let (sin, cos) = v.evaluate(value).sin_cos();
-
-                match trig {
-                    Trig::Sin => sin,
-                    Trig::Cos => cos,
-                    Trig::Tan => sin / cos,
-                    Trig::Csc => sin.recip(),
-                    Trig::Sec => sin.recip(),
-                    Trig::Cot => cos / sin,
-                }
-            }
-            Expr::Var => value,
-            Expr::Num(n) => n,
+            Expr::Bin(t, ..) => t.precedence(),
+            Expr::Trig(..) | Expr::Derivative(..) | Expr::Func(..) => 3,
+            Expr::Unknown(..) | Expr::Param(_) | Expr::Var | Expr::Num(_) => 100,
         }
     }
+
+    pub fn evaluate(&self, value: f64) -> f64 {
+        self.evaluate_scalar(value)
+    }
 }
 
 impl From<f64> for Expr {
@@ -0,0 +1,151 @@
+use std::ops::RangeInclusive;
+
+use crate::prelude::*;
+
+/// A second derivative smaller than this is taken as 0, and the first derivative test is used.
+const FLAT: f64 = 1e-8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointKind {
+    Minimum,
+    Maximum,
+    /// The derivative is 0 but has the same sign on both sides, like `x^3` at 0.
+    Inflection,
+}
+
+/// A point where the derivative is 0.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CriticalPoint {
+    pub x: f64,
+    /// The exact point is within `error` of `x`.
+    pub error: f64,
+    /// The value of the expr at `x`.
+    pub value: f64,
+    pub kind: PointKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Monotonicity {
+    Increasing,
+    Decreasing,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Concavity {
+    Up,
+    Down,
+}
+
+/// The shape of an expr on an interval, created by [`Expr::analyze`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Analysis {
+    pub critical_points: Vec<CriticalPoint>,
+    /// Where the second derivative changes sign.
+    pub inflection_points: Vec<f64>,
+    /// The interval split where the expr changes direction.
+    pub monotonicity: Vec<(RangeInclusive<f64>, Monotonicity)>,
+    /// The interval split at every inflection point.
+    pub concavity: Vec<(RangeInclusive<f64>, Concavity)>,
+}
+
+impl Expr {
+    /// Every point of the interval where the derivative is 0, classified with the second
+    /// derivative test, or the first derivative test when the second derivative is 0 too.
+    pub fn critical_points(&self, interval: RangeInclusive<f64>) -> Vec<CriticalPoint> {
+        let f = self.clone().expand_derivatives();
+        let d1 = f.clone().derive();
+        let d2 = d1.clone().derive();
+
+        d1.roots(interval)
+            .into_iter()
+            .map(|root| {
+                let x = root.x;
+                let s = d2.evaluate(x);
+                let kind = if s > FLAT {
+                    PointKind::Minimum
+                } else if s < -FLAT {
+                    PointKind::Maximum
+                } else {
+                    let h = (1e-4 * (1.0 + x.abs())).max(10.0 * root.error);
+                    match (d1.evaluate(x - h) < 0.0, d1.evaluate(x + h) < 0.0) {
+                        (true, false) => PointKind::Minimum,
+                        (false, true) => PointKind::Maximum,
+                        _ => PointKind::Inflection,
+                    }
+                };
+
+                CriticalPoint {
+                    x,
+                    error: root.error,
+                    value: f.evaluate(x),
+                    kind,
+                }
+            })
+            .collect()
+    }
+
+    /// The critical points, inflection points, and where the expr is increasing or decreasing
+    /// and concave up or down on the interval.
+    pub fn analyze(&self, interval: RangeInclusive<f64>) -> Analysis {
+        let f = self.clone().expand_derivatives();
+        let d1 = f.clone().derive();
+        let d2 = d1.clone().derive();
+
+        let critical_points = f.critical_points(interval.clone());
+        let inflection_points: Vec<_> = d2
+            .roots(interval.clone())
+            .into_iter()
+            .map(|r| r.x)
+            .filter(|&x| {
+                let h = 1e-4 * (1.0 + x.abs());
+                (d2.evaluate(x - h) < 0.0) != (d2.evaluate(x + h) < 0.0)
+            })
+            .collect();
+
+        let monotonicity = split(&interval, critical_points.iter().map(|p| p.x), |x| {
+            if d1.evaluate(x) < 0.0 {
+                Monotonicity::Decreasing
+            } else {
+                Monotonicity::Increasing
+            }
+        });
+        let concavity = split(&interval, inflection_points.iter().copied(), |x| {
+            if d2.evaluate(x) < 0.0 {
+                Concavity::Down
+            } else {
+                Concavity::Up
+            }
+        });
+
+        Analysis {
+            critical_points,
+            inflection_points,
+            monotonicity,
+            concavity,
+        }
+    }
+}
+
+/// Split the interval at every point, with the value of `at` in the middle of each part.
+/// Parts next to each other with the same value are merged.
+fn split<T: PartialEq>(
+    interval: &RangeInclusive<f64>,
+    points: impl Iterator<Item = f64>,
+    at: impl Fn(f64) -> T,
+) -> Vec<(RangeInclusive<f64>, T)> {
+    let (start, end) = (*interval.start(), *interval.end());
+    let bounds: Vec<_> = std::iter::once(start)
+        .chain(points.filter(|&x| x > start && x < end))
+        .chain(std::iter::once(end))
+        .collect();
+
+    let mut parts: Vec<(RangeInclusive<f64>, T)> = vec![];
+    for w in bounds.windows(2) {
+        let value = at((w[0] + w[1]) / 2.0);
+        match parts.last_mut() {
+            Some((range, last)) if *last == value => *range = *range.start()..=w[1],
+            _ => parts.push((w[0]..=w[1], value)),
+        }
+    }
+    parts
+}
@@ -0,0 +1,67 @@
+//! Turn exprs into source code of other languages, so derivatives can be computed once and
+//! evaluated without this crate.
+
+mod c;
+mod rust;
+
+/// A piece of generated code and how tightly it binds, so the caller know when to wrap it in
+/// parentheses.
+struct Code {
+    text: String,
+    prec: Prec,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Prec {
+    Add,
+    Mul,
+    /// A unary minus, like a negative number.
+    Neg,
+    /// Anything that never need parentheses, like a name, a call or a literal.
+    Atom,
+}
+
+impl Code {
+    fn new(text: impl Into<String>, prec: Prec) -> Self {
+        Self {
+            text: text.into(),
+            prec,
+        }
+    }
+
+    /// The code, wrapped in parentheses if it binds less tightly than `prec`.
+    fn at(self, prec: Prec) -> String {
+        if self.prec < prec {
+            format!("({})", self.text)
+        } else {
+            self.text
+        }
+    }
+}
+
+/// Write a binary operator, as every operator is left associative the right side is wrapped
+/// when it has the same precedence to keep the evaluation order of the tree.
+fn infix(a: Code, op: &str, b: Code, prec: Prec) -> Code {
+    let a = a.at(prec);
+    let b = if b.prec <= prec {
+        format!("({})", b.text)
+    } else {
+        b.text
+    };
+    Code::new(format!("{a} {op} {b}"), prec)
+}
+
+/// The name of the function for the nth derivative of an unknown function, which has to be
+/// defined next to the generated one: `f`, then `f_d1`, `f_d2`...
+fn unknown(name: &str, order: usize) -> String {
+    if order == 0 {
+        name.into()
+    } else {
+        format!("{name}_d{order}")
+    }
+}
+
+/// The integer value of a number if it is a whole number that fit in an `i32`.
+fn integer(n: f64) -> Option<i32> {
+    (n.fract() == 0.0 && n.abs() <= i32::MAX as f64).then_some(n as i32)
+}
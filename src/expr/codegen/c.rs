@@ -0,0 +1,124 @@
+use std::f64::consts::E;
+
+use super::{infix, integer, unknown, Code, Prec};
+use crate::{
+    Bin::*,
+    Expr::{self, *},
+    Trig::*,
+};
+
+/// Integer powers up to this are written as repeated multiplications.
+const MAX_MUL_POWER: i32 = 8;
+
+/// C and GLSL only differ in the types and some functions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Dialect {
+    C,
+    Glsl,
+}
+
+impl Expr {
+    /// Write this expr as a C99 function `double fn_name(double x)`, using `math.h`.
+    pub fn to_c(&self, fn_name: &str) -> String {
+        format!(
+            "double {fn_name}(double x) {{\n    return {};\n}}\n",
+            Dialect::C.emit(self).text
+        )
+    }
+
+    /// Write this expr as a GLSL function `float fn_name(float x)`.
+    pub fn to_glsl(&self, fn_name: &str) -> String {
+        format!(
+            "float {fn_name}(float x) {{\n    return {};\n}}\n",
+            Dialect::Glsl.emit(self).text
+        )
+    }
+}
+
+impl Dialect {
+    fn emit(self, expr: &Expr) -> Code {
+        match expr {
+            Bin(Add, a, b) => infix(self.emit(a), "+", self.emit(b), Prec::Add),
+            Bin(Sub, a, b) => infix(self.emit(a), "-", self.emit(b), Prec::Add),
+            Bin(Mul, a, b) => infix(self.emit(a), "*", self.emit(b), Prec::Mul),
+            Bin(Div, a, b) => infix(self.emit(a), "/", self.emit(b), Prec::Mul),
+
+            Bin(Exp, a, b) => match (&**a, &**b) {
+                (Num(E), b) => self.call("exp", &[b]),
+                (Num(n), b) if *n == 2.0 => self.call("exp2", &[b]),
+                (a, Num(n)) if *n == 0.5 => self.call("sqrt", &[a]),
+                (a, Num(n)) => match integer(*n) {
+                    Some(0) => Code::new("1.0", Prec::Atom),
+                    Some(p) if p.abs() <= MAX_MUL_POWER => {
+                        let base = self.emit(a).at(Prec::Neg);
+                        let product = vec![base; p.unsigned_abs() as usize].join(" * ");
+                        match p {
+                            1 => Code::new(product, Prec::Neg),
+                            p if p > 0 => Code::new(product, Prec::Mul),
+                            -1 => Code::new(format!("1.0 / {product}"), Prec::Mul),
+                            _ => Code::new(format!("1.0 / ({product})"), Prec::Mul),
+                        }
+                    }
+                    _ => self.call("pow", &[a, b]),
+                },
+                (a, b) => self.call("pow", &[a, b]),
+            },
+
+            Bin(Log, a, b) => match (&**a, self) {
+                (Num(E), _) => self.call("log", &[b]),
+                (Num(n), _) if *n == 2.0 => self.call("log2", &[b]),
+                (Num(n), Dialect::C) if *n == 10.0 => self.call("log10", &[b]),
+                (a, _) => infix(
+                    self.call("log", &[b]),
+                    "/",
+                    self.call("log", &[a]),
+                    Prec::Mul,
+                ),
+            },
+
+            Trig(t, a) => match t {
+                Sin => self.call("sin", &[a]),
+                Cos => self.call("cos", &[a]),
+                Tan => self.call("tan", &[a]),
+                Csc => self.recip(self.call("sin", &[a])),
+                Sec => self.recip(self.call("cos", &[a])),
+                Cot => self.recip(self.call("tan", &[a])),
+            },
+
+            Func(func, a) => self.call(func.name(), &[a]),
+
+            Unknown(name, n, a) => self.call(&unknown(name, *n), &[a]),
+
+            Derivative(..) => self.emit(&expr.clone().expand_derivatives()),
+
+            Param(name) => Code::new(name.clone(), Prec::Atom),
+            Var => Code::new("x", Prec::Atom),
+            Num(n) => self.num(*n),
+        }
+    }
+
+    fn num(self, n: f64) -> Code {
+        match self {
+            _ if n.is_finite() && n.is_sign_negative() => Code::new(format!("{n:?}"), Prec::Neg),
+            _ if n.is_finite() => Code::new(format!("{n:?}"), Prec::Atom),
+
+            Dialect::C if n.is_nan() => Code::new("NAN", Prec::Atom),
+            Dialect::C if n > 0.0 => Code::new("INFINITY", Prec::Atom),
+            Dialect::C => Code::new("-INFINITY", Prec::Neg),
+
+            // GLSL has no constant for these
+            Dialect::Glsl if n.is_nan() => Code::new("(0.0 / 0.0)", Prec::Atom),
+            Dialect::Glsl if n > 0.0 => Code::new("(1.0 / 0.0)", Prec::Atom),
+            Dialect::Glsl => Code::new("(-1.0 / 0.0)", Prec::Atom),
+        }
+    }
+
+    fn call(self, func: &str, args: &[&Expr]) -> Code {
+        let args: Vec<_> = args.iter().map(|a| self.emit(a).text).collect();
+        Code::new(format!("{func}({})", args.join(", ")), Prec::Atom)
+    }
+
+    fn recip(self, code: Code) -> Code {
+        infix(Code::new("1.0", Prec::Atom), "/", code, Prec::Mul)
+    }
+}
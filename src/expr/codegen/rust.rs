@@ -0,0 +1,92 @@
+use std::f64::consts::E;
+
+use super::{infix, integer, unknown, Code, Prec};
+use crate::{
+    Bin::*,
+    Expr::{self, *},
+    Trig::*,
+};
+
+impl Expr {
+    /// Write this expr as a rust function `fn fn_name(x: f64) -> f64`, using the methods of
+    /// `f64`. Useful to emit derivatives from a build script.
+    pub fn to_rust(&self, fn_name: &str) -> String {
+        format!(
+            "fn {fn_name}(x: f64) -> f64 {{\n    {}\n}}\n",
+            emit(self).text
+        )
+    }
+}
+
+fn emit(expr: &Expr) -> Code {
+    match expr {
+        Bin(Add, a, b) => infix(emit(a), "+", emit(b), Prec::Add),
+        Bin(Sub, a, b) => infix(emit(a), "-", emit(b), Prec::Add),
+        Bin(Mul, a, b) => infix(emit(a), "*", emit(b), Prec::Mul),
+        Bin(Div, a, b) => infix(emit(a), "/", emit(b), Prec::Mul),
+
+        Bin(Exp, a, b) => match (&**a, &**b) {
+            (Num(E), b) => method(b, "exp()"),
+            (Num(n), b) if *n == 2.0 => method(b, "exp2()"),
+            (a, Num(n)) if *n == 0.5 => method(a, "sqrt()"),
+            (a, Num(n)) if integer(*n).is_some() => method(a, &format!("powi({n})")),
+            (a, b) => method(a, &format!("powf({})", emit(b).text)),
+        },
+
+        Bin(Log, a, b) => match &**a {
+            Num(E) => method(b, "ln()"),
+            Num(n) if *n == 10.0 => method(b, "log10()"),
+            Num(n) if *n == 2.0 => method(b, "log2()"),
+            a => method(b, &format!("log({})", emit(a).text)),
+        },
+
+        Trig(t, a) => method(
+            a,
+            match t {
+                Sin => "sin()",
+                Cos => "cos()",
+                Tan => "tan()",
+                Csc => "sin().recip()",
+                Sec => "cos().recip()",
+                Cot => "tan().recip()",
+            },
+        ),
+
+        // the function has to be defined next to the generated one
+        Func(func, a) => Code::new(format!("{}({})", func.name(), emit(a).text), Prec::Atom),
+
+        Unknown(name, n, a) => Code::new(
+            format!("{}({})", unknown(name, *n), emit(a).text),
+            Prec::Atom,
+        ),
+
+        Derivative(..) => emit(&expr.clone().expand_derivatives()),
+
+        // the param has to be defined next to the generated function too
+        Param(name) => Code::new(name.clone(), Prec::Atom),
+        Var => Code::new("x", Prec::Atom),
+        Num(E) => Code::new("std::f64::consts::E", Prec::Atom),
+        Num(n) if n.is_nan() => Code::new("f64::NAN", Prec::Atom),
+        Num(n) if n.is_infinite() && *n > 0.0 => Code::new("f64::INFINITY", Prec::Atom),
+        Num(n) if n.is_infinite() => Code::new("f64::NEG_INFINITY", Prec::Atom),
+        Num(n) if n.is_sign_negative() => Code::new(format!("{n:?}"), Prec::Neg),
+        Num(n) => Code::new(format!("{n:?}"), Prec::Atom),
+    }
+}
+
+/// Call a method of `f64` on an expr, literals need a suffix to be a receiver.
+fn method(receiver: &Expr, call: &str) -> Code {
+    let receiver = match receiver {
+        Num(n) if n.is_finite() && *n != E => {
+            let literal = format!("{n:?}_f64");
+            if n.is_sign_negative() {
+                format!("({literal})")
+            } else {
+                literal
+            }
+        }
+        _ => emit(receiver).at(Prec::Atom),
+    };
+
+    Code::new(format!("{receiver}.{call}"), Prec::Atom)
+}
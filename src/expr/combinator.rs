@@ -1,10 +1,12 @@
 use std::f64::consts::E;
+use std::sync::Arc;
 
 use super::{
     Bin::*,
     Expr::{self, *},
-    Trig,
+    Function, Trig,
 };
+use crate::lang::{is_ident, LangError};
 
 #[allow(clippy::should_implement_trait)]
 impl Expr {
@@ -64,6 +66,33 @@ impl Expr {
         Trig(func, Box::new(self))
     }
 
+    /// Apply a user defined function to this value.
+    pub fn func(self, func: &Arc<Function>) -> Self {
+        Func(func.clone(), Box::new(self))
+    }
+
+    /// Apply the nth derivative of an unknown function to this value, like `f''(x)`.
+    pub fn unknown(self, name: impl Into<String>, order: usize) -> Self {
+        Unknown(name.into(), order, Box::new(self))
+    }
+
+    /// The nth derivative of this expr, without deriving it yet. See [`Expr::derive`] to derive
+    /// right away.
+    pub fn derivative(self, order: usize) -> Self {
+        Derivative(order, Box::new(self))
+    }
+
+    /// A named value other than `x`, see [`Expr::Param`].
+    ///
+    /// The name has to be a word that is not already a keyword, so `$name` reads it back.
+    pub fn param(name: impl Into<String>) -> Result<Self, LangError> {
+        let name = name.into();
+        if !is_ident(&name) {
+            return Err(LangError::InvalidWord(name, 0));
+        }
+        Ok(Param(name))
+    }
+
     pub fn neg(self) -> Self {
         self.mul(-1.0)
     }
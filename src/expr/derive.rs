@@ -68,13 +68,14 @@ impl Expr {
                 // log_a f(x) -> f'(x)/(f(x) ln a)
                 (a @ Num(_), f) => f.clone().derive().div(f.mul(a.ln())),
 
-                // log_x a -> (ln a)/(x (ln x)^2)
-                (x @ Var, a @ Num(_)) => a.ln().div(x.clone().mul(x.ln().exp(2.0))),
+                // log_x a -> -(ln a)/(x (ln x)^2)
+                (x @ Var, a @ Num(_)) => a.ln().neg().div(x.clone().mul(x.ln().exp(2.0))),
 
-                // log_f(x) a -> (ln a)/(f(x) (ln f(x))^2) ln f(x)
+                // log_f(x) a -> -(ln a)/(f(x) (ln f(x))^2) f'(x)
                 (f, a @ Num(_)) => a
                     .ln()
-                    .div(f.clone().mul(f.clone().exp(2.0).ln()))
+                    .neg()
+                    .div(f.clone().mul(f.clone().ln().exp(2.0)))
                     .mul(f.derive()),
 
                 // log_f(x) g(x) -> ((g'(x) ln f(x)) / g(x) - (f'(x) ln g(x) / f(x))) / (ln f(x))^2
@@ -97,8 +98,46 @@ impl Expr {
             }
             .mul(f.derive()),
 
+            Derivative(n, f) => f.derive_n(n + 1),
+
+            // g(f(x)) -> g'(f(x)) * f'(x)
+            Func(func, f) => {
+                let derivative = func.derivative();
+                match *f {
+                    Var => derivative,
+                    f => derivative.compose(f.clone()).mul(f.derive()),
+                }
+            }
+
+            // f^(n)(g(x)) -> f^(n+1)(g(x)) * g'(x)
+            Unknown(name, n, f) => match *f {
+                Var => Var.unknown(name, n + 1),
+                f => f.clone().unknown(name, n + 1).mul(f.derive()),
+            },
+
             Var => Num(1.0),
-            Num(_) => Num(0.0),
+            Param(_) | Num(_) => Num(0.0),
+        }
+    }
+
+    /// Derive this expr `n` times.
+    pub fn derive_n(self, n: usize) -> Self {
+        (0..n).fold(self, |f, _| f.derive())
+    }
+
+    /// Replace every [`Derivative`] node by the derived expr.
+    pub fn expand_derivatives(self) -> Self {
+        match self {
+            Bin(t, a, b) => Bin(
+                t,
+                Box::new(a.expand_derivatives()),
+                Box::new(b.expand_derivatives()),
+            ),
+            Trig(t, a) => a.expand_derivatives().trig(t),
+            Func(func, a) => a.expand_derivatives().func(&func),
+            Unknown(name, n, a) => a.expand_derivatives().unknown(name, n),
+            Derivative(n, f) => f.expand_derivatives().derive_n(n),
+            e => e,
         }
     }
 }
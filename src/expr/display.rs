@@ -1,56 +1,185 @@
 use std::f64::consts::E;
-use std::fmt::Display;
+use std::fmt::{Display, Formatter, Result};
 
+use super::format::{Notation, NumFormat, NumRepr};
 use crate::{
     Bin::*,
     Expr::{self, *},
     Trig::*,
 };
 
+/// Printing only adds parentheses where they are needed for the parser to read back the exact
+/// same tree, so `s.parse::<Expr>()` of a printed expr always gives back the expr (as long as
+/// every number is finite).
+///
+/// The precision of the formatter is used as the number of significant digits, so `{:.3}` can
+/// be used for a short but lossy output.
 impl Display for Expr {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            // catch all cases
-            Bin(t, a, b) => {
-                let mut a_str = a.to_string();
-                let mut b_str = b.to_string();
-
-                if a.precedence() < self.precedence() {
-                    a_str = format!("({a})");
-                }
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let format = NumFormat {
+            digits: f.precision(),
+            notation: match f.precision() {
+                Some(_) => Notation::Auto,
+                None => Notation::Fixed,
+            },
+            ..Default::default()
+        };
+        Printer { format }.write_expr(self, f, true)
+    }
+}
 
-                if b.precedence() < self.precedence() {
-                    b_str = format!("({b})");
-                }
+/// An expr printed with custom number formatting, created by [`Expr::display_with`].
+pub struct Formatted<'a> {
+    expr: &'a Expr,
+    format: NumFormat,
+}
 
-                match t {
-                    Add => write!(f, "{a_str} + {b_str}"),
-                    Sub => write!(f, "{a_str} - {b_str}"),
-                    Mul => write!(f, "{a_str} * {b_str}"),
-                    Div => write!(f, "{a_str} / {b_str}"),
-                    Exp => write!(f, "{a_str}^{b_str}"),
-                    Log if matches!(**a, Self::Num(E)) => write!(f, "ln {b_str}"),
-                    Log => write!(f, "log_{a_str} {b_str}"),
-                }
+impl Expr {
+    /// Print this expr like `Display` but with numbers written with `format`. Fractions and
+    /// logs are not read back as numbers by the parser.
+    pub fn display_with(&self, format: NumFormat) -> Formatted<'_> {
+        Formatted { expr: self, format }
+    }
+}
+
+impl Display for Formatted<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Printer {
+            format: self.format,
+        }
+        .write_expr(self.expr, f, true)
+    }
+}
+
+struct Printer {
+    format: NumFormat,
+}
+
+impl Printer {
+    /// Write an expr, `leading` is true when nothing has been written before it in the current
+    /// group so a negative number does not need parentheses.
+    fn write_expr(&self, expr: &Expr, f: &mut Formatter<'_>, leading: bool) -> Result {
+        match expr {
+            // every binary operator is parsed left associative, so an operand on the right with the
+            // same precedence need parentheses
+            Bin(t @ (Add | Sub | Mul | Div), a, b) => {
+                self.write_operand(a, f, self.precedence(a) < self.precedence(expr), leading)?;
+                let op = match t {
+                    Add => "+",
+                    Sub => "-",
+                    Mul => "*",
+                    Div => "/",
+                    _ => unreachable!(),
+                };
+                write!(f, " {op} ")?;
+                self.write_operand(b, f, self.precedence(b) <= self.precedence(expr), false)
+            }
+
+            // `x^y^z` is always written with parentheses as it is hard to read either way
+            Bin(Exp, a, b) => {
+                self.write_operand(a, f, !self.is_atom(a), leading)?;
+                write!(f, "^")?;
+                self.write_operand(b, f, !self.is_atom(b), false)
             }
 
-            Trig(t, a) => write!(
-                f,
-                "{} {}",
-                match t {
+            Bin(Log, a, b) if matches!(**a, Num(E)) => {
+                write!(f, "ln ")?;
+                self.write_operand(b, f, !self.is_arg(b), false)
+            }
+            Bin(Log, a, b) => {
+                write!(f, "log_")?;
+                self.write_operand(a, f, !self.is_arg(a), false)?;
+                write!(f, " ")?;
+                self.write_operand(b, f, !self.is_arg(b), false)
+            }
+
+            Trig(t, a) => {
+                let name = match t {
                     Sin => "sin",
                     Cos => "cos",
                     Tan => "tan",
                     Csc => "csc",
                     Sec => "sec",
                     Cot => "cot",
-                },
-                a
-            ),
+                };
+                write!(f, "{name} ")?;
+                self.write_operand(a, f, !self.is_arg(a), false)
+            }
 
+            Func(func, a) => {
+                write!(f, "{} ", func.name())?;
+                self.write_operand(a, f, !self.is_arg(a), false)
+            }
+
+            Unknown(name, n, a) => {
+                write!(f, "{name}{}(", "'".repeat(*n))?;
+                self.write_expr(a, f, true)?;
+                write!(f, ")")
+            }
+
+            Derivative(1, a) => {
+                write!(f, "d/dx ")?;
+                self.write_operand(a, f, !self.is_arg(a), false)
+            }
+            Derivative(n, a) => {
+                write!(f, "d^{n}/dx^{n} ")?;
+                self.write_operand(a, f, !self.is_arg(a), false)
+            }
+
+            Param(name) => write!(f, "${name}"),
             Var => write!(f, "x"),
             Num(E) => write!(f, "e"),
-            Num(n) => write!(f, "{n}"),
+            Num(n) => {
+                let repr = self.format.repr(*n);
+                if repr.is_negative() && !leading {
+                    write!(f, "({})", repr.text())
+                } else {
+                    write!(f, "{}", repr.text())
+                }
+            }
+        }
+    }
+
+    fn write_operand(
+        &self,
+        expr: &Expr,
+        f: &mut Formatter<'_>,
+        paren: bool,
+        leading: bool,
+    ) -> Result {
+        if paren {
+            write!(f, "(")?;
+            self.write_expr(expr, f, true)?;
+            write!(f, ")")
+        } else {
+            self.write_expr(expr, f, leading)
+        }
+    }
+
+    /// Whether the expr can be written without parentheses as a base or a power.
+    fn is_atom(&self, expr: &Expr) -> bool {
+        match expr {
+            Var | Num(E) | Unknown(..) | Param(_) => true,
+            Num(n) => matches!(self.format.repr(*n), NumRepr::Decimal(..)) && !n.is_sign_negative(),
+            _ => false,
+        }
+    }
+
+    /// Whether the expr can be written without parentheses as the argument of a function.
+    fn is_arg(&self, expr: &Expr) -> bool {
+        self.is_atom(expr) || matches!(expr, Bin(Exp, ..))
+    }
+
+    /// The precedence of an expr as it is written, a number written as a fraction or a log is
+    /// not a unit anymore.
+    fn precedence(&self, expr: &Expr) -> usize {
+        match expr {
+            Num(n) if *n != E => match self.format.repr(*n) {
+                NumRepr::Fraction(..) => Div.precedence(),
+                NumRepr::Ln(_) => Log.precedence(),
+                NumRepr::Decimal(..) => expr.precedence(),
+            },
+            _ => expr.precedence(),
         }
     }
 }
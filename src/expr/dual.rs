@@ -0,0 +1,197 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::{prelude::*, Function};
+
+/// A number the evaluator can run an expr over, see [`Expr::evaluate_scalar`].
+pub trait Scalar:
+    Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+{
+    /// A number that does not depend on `x`.
+    fn constant(value: f64) -> Self;
+    fn powf(self, power: Self) -> Self;
+    /// The log of this number in the given base.
+    fn log(self, base: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    /// Apply a user defined function to this number.
+    fn func(self, func: &Function) -> Self;
+}
+
+impl Scalar for f64 {
+    fn constant(value: f64) -> Self {
+        value
+    }
+
+    fn powf(self, power: Self) -> Self {
+        f64::powf(self, power)
+    }
+
+    fn log(self, base: Self) -> Self {
+        f64::log(self, base)
+    }
+
+    fn sin(self) -> Self {
+        f64::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        f64::cos(self)
+    }
+
+    fn func(self, func: &Function) -> Self {
+        func.eval(self)
+    }
+}
+
+/// A dual number `value + derivative ε` with `ε^2 = 0`. Evaluating an expr at
+/// [`Dual::var`] gives its value and its derivative at that point in one pass.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dual {
+    pub value: f64,
+    pub derivative: f64,
+}
+
+impl Dual {
+    pub fn new(value: f64, derivative: f64) -> Self {
+        Self { value, derivative }
+    }
+
+    /// `x` at the given value, with a derivative of 1.
+    pub fn var(value: f64) -> Self {
+        Self::new(value, 1.0)
+    }
+}
+
+impl Add for Dual {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.value + rhs.value, self.derivative + rhs.derivative)
+    }
+}
+
+impl Sub for Dual {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.value - rhs.value, self.derivative - rhs.derivative)
+    }
+}
+
+impl Mul for Dual {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.value * rhs.value,
+            self.derivative * rhs.value + self.value * rhs.derivative,
+        )
+    }
+}
+
+impl Div for Dual {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Self::new(
+            self.value / rhs.value,
+            (self.derivative * rhs.value - self.value * rhs.derivative) / (rhs.value * rhs.value),
+        )
+    }
+}
+
+impl Neg for Dual {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.value, -self.derivative)
+    }
+}
+
+impl Scalar for Dual {
+    fn constant(value: f64) -> Self {
+        Self::new(value, 0.0)
+    }
+
+    fn powf(self, power: Self) -> Self {
+        let value = self.value.powf(power.value);
+        // a constant power does not need the log of the base, which can be negative
+        let derivative = if power.derivative == 0.0 {
+            power.value * self.value.powf(power.value - 1.0) * self.derivative
+        } else {
+            value
+                * (power.derivative * self.value.ln() + power.value * self.derivative / self.value)
+        };
+        Self::new(value, derivative)
+    }
+
+    fn log(self, base: Self) -> Self {
+        let (ln_a, ln_b) = (base.value.ln(), self.value.ln());
+        Self::new(
+            self.value.log(base.value),
+            (self.derivative / self.value * ln_a - base.derivative / base.value * ln_b)
+                / (ln_a * ln_a),
+        )
+    }
+
+    fn sin(self) -> Self {
+        let (sin, cos) = self.value.sin_cos();
+        Self::new(sin, cos * self.derivative)
+    }
+
+    fn cos(self) -> Self {
+        let (sin, cos) = self.value.sin_cos();
+        Self::new(cos, -sin * self.derivative)
+    }
+
+    fn func(self, func: &Function) -> Self {
+        let derivative = func.derivative().evaluate(self.value);
+        Self::new(func.eval(self.value), derivative * self.derivative)
+    }
+}
+
+impl Expr {
+    /// Evaluate the expr over any [`Scalar`], with `x` at the given value. An unknown
+    /// function or a param is NaN.
+    pub fn evaluate_scalar<S: Scalar>(&self, x: S) -> S {
+        match self {
+            Bin(bin, a, b) => {
+                let a = a.evaluate_scalar(x);
+                let b = b.evaluate_scalar(x);
+
+                match bin {
+                    Add => a + b,
+                    Sub => a - b,
+                    Div => a / b,
+                    Mul => a * b,
+                    Exp => a.powf(b),
+                    Log => b.log(a),
+                }
+            }
+            Trig(trig, v) => {
+                let v = v.evaluate_scalar(x);
+                let (sin, cos) = (v.sin(), v.cos());
+                let one = S::constant(1.0);
+
+                match trig {
+                    Sin => sin,
+                    Cos => cos,
+                    Tan => sin / cos,
+                    Csc => one / sin,
+                    Sec => one / cos,
+                    Cot => cos / sin,
+                }
+            }
+            Derivative(n, f) => f.as_ref().clone().derive_n(*n).evaluate_scalar(x),
+            Func(func, v) => v.evaluate_scalar(x).func(func),
+            Unknown(..) | Param(_) => S::constant(f64::NAN),
+            Var => x,
+            Num(n) => S::constant(*n),
+        }
+    }
+
+    /// The value and the derivative at `x` in one pass, without deriving the expr.
+    pub fn evaluate_dual(&self, x: f64) -> Dual {
+        self.evaluate_scalar(Dual::var(x))
+    }
+}
@@ -0,0 +1,165 @@
+use crate::prelude::*;
+
+/// Integer powers of a sum are only expanded up to this, as the number of terms doubles with
+/// each power.
+const MAX_EXPAND_POWER: f64 = 12.0;
+
+impl Expr {
+    /// Distribute products and quotients over sums and expand integer powers of sums, so
+    /// `(x + 1)^2` gives `x * x + x * 1 + (1 * x + 1 * 1)`. Use [`Expr::collect`] after to
+    /// group the terms.
+    pub fn expand(self) -> Self {
+        match self {
+            Bin(Mul, a, b) => distribute(a.expand(), b.expand()),
+            Bin(Div, a, b) => divide(a.expand(), b.expand()),
+            Bin(Exp, a, b) => match (a.expand(), b.expand()) {
+                (a @ Bin(Add | Sub, ..), Num(n))
+                    if n.fract() == 0.0 && (2.0..=MAX_EXPAND_POWER).contains(&n) =>
+                {
+                    (1..n as usize).fold(a.clone(), |acc, _| distribute(acc, a.clone()))
+                }
+                (a, b) => a.exp(b),
+            },
+            Bin(t, a, b) => Bin(t, Box::new(a.expand()), Box::new(b.expand())),
+            Trig(t, a) => a.expand().trig(t),
+            Func(func, a) => a.expand().func(&func),
+            Unknown(name, n, a) => a.expand().unknown(name, n),
+            Derivative(n, a) => a.expand().derivative(n),
+            e => e,
+        }
+    }
+
+    /// Group the terms of a sum by power of `x` and add their coefficients, with the highest
+    /// power first, like `3 * x^2 + 2 * x + 1`. A term that is not a number times a power of
+    /// `x` is grouped with the terms that have the same other factors.
+    pub fn collect(self) -> Self {
+        let mut terms = vec![];
+        flatten_sum(self, 1.0, &mut terms);
+
+        let mut groups: Vec<Term> = vec![];
+        for term in terms {
+            match groups
+                .iter_mut()
+                .find(|g| g.power == term.power && g.rest == term.rest)
+            {
+                Some(group) => group.coef += term.coef,
+                None => groups.push(term),
+            }
+        }
+
+        groups.retain(|g| g.coef != 0.0);
+        groups.sort_by(|a, b| b.power.total_cmp(&a.power));
+
+        let mut groups = groups.into_iter();
+        let Some(first) = groups.next() else {
+            return Num(0.0);
+        };
+        groups.fold(first.into_expr(), |sum, term| {
+            if term.coef < 0.0 {
+                sum.sub(
+                    Term {
+                        coef: -term.coef,
+                        ..term
+                    }
+                    .into_expr(),
+                )
+            } else {
+                sum.add(term.into_expr())
+            }
+        })
+    }
+}
+
+fn distribute(a: Expr, b: Expr) -> Expr {
+    match (a, b) {
+        (Bin(t @ (Add | Sub), p, q), b) => Bin(
+            t,
+            Box::new(distribute(*p, b.clone())),
+            Box::new(distribute(*q, b)),
+        ),
+        (a, Bin(t @ (Add | Sub), p, q)) => Bin(
+            t,
+            Box::new(distribute(a.clone(), *p)),
+            Box::new(distribute(a, *q)),
+        ),
+        (a, b) => a.mul(b),
+    }
+}
+
+fn divide(a: Expr, b: Expr) -> Expr {
+    match a {
+        Bin(t @ (Add | Sub), p, q) => {
+            Bin(t, Box::new(divide(*p, b.clone())), Box::new(divide(*q, b)))
+        }
+        a => a.div(b),
+    }
+}
+
+/// A term `coef * x^power * rest`.
+struct Term {
+    coef: f64,
+    power: f64,
+    rest: Vec<Expr>,
+}
+
+impl Term {
+    fn into_expr(self) -> Expr {
+        let x = match self.power {
+            0.0 => None,
+            1.0 => Some(Var),
+            p => Some(Var.exp(p)),
+        };
+        let mut factors = x.into_iter().chain(self.rest);
+
+        match factors.next() {
+            None => Num(self.coef),
+            Some(first) => {
+                let product = factors.fold(first, Expr::mul);
+                if self.coef == 1.0 {
+                    product
+                } else {
+                    Num(self.coef).mul(product)
+                }
+            }
+        }
+    }
+}
+
+fn flatten_sum(expr: Expr, sign: f64, terms: &mut Vec<Term>) {
+    match expr {
+        Bin(Add, a, b) => {
+            flatten_sum(*a, sign, terms);
+            flatten_sum(*b, sign, terms);
+        }
+        Bin(Sub, a, b) => {
+            flatten_sum(*a, sign, terms);
+            flatten_sum(*b, -sign, terms);
+        }
+        e => {
+            let mut term = Term {
+                coef: sign,
+                power: 0.0,
+                rest: vec![],
+            };
+            factor(e, &mut term);
+            terms.push(term);
+        }
+    }
+}
+
+fn factor(expr: Expr, term: &mut Term) {
+    match expr {
+        Bin(Mul, a, b) => {
+            factor(*a, term);
+            factor(*b, term);
+        }
+        Bin(Div, a, b) if b.is_num() => {
+            factor(*a, term);
+            term.coef /= b.evaluate(0.0);
+        }
+        Num(n) => term.coef *= n,
+        Var => term.power += 1.0,
+        Bin(Exp, a, b) if a.is_var() && b.is_num() => term.power += b.evaluate(0.0),
+        e => term.rest.push(e),
+    }
+}
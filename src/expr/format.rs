@@ -0,0 +1,179 @@
+/// The notation used for numbers when printing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Notation {
+    /// Fixed for numbers of reasonable size, scientific for very big or very small ones.
+    #[default]
+    Auto,
+    /// Always write every digit before the decimal point, like `1200`.
+    Fixed,
+    /// Always write with a power of ten, like `1.2e3`.
+    Scientific,
+}
+
+/// Options on how numbers are written, used by [`Expr::display_with`](crate::Expr::display_with),
+/// [`Expr::pretty_with`](crate::Expr::pretty_with) and [`Expr::latex_with`](crate::Expr::latex_with).
+///
+/// The default writes the shortest digits that give back the same number, in scientific notation
+/// when it is very big or very small. `Display` without a precision writes them in fixed notation,
+/// which the parser reads back.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NumFormat {
+    /// Number of significant digits, `None` for as many as needed.
+    pub digits: Option<usize>,
+    pub notation: Notation,
+    /// Write numbers that are a fraction with a small denominator as `a/b`.
+    pub fractions: bool,
+    /// Write numbers that are the natural log of a small integer as `ln n`.
+    pub symbolic_ln: bool,
+}
+
+/// The largest denominator tried when looking for a fraction.
+const MAX_DENOMINATOR: i64 = 1000;
+/// The largest integer tried when looking for a log.
+const MAX_LN: u32 = 100;
+
+/// How a single number should be written.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum NumRepr {
+    /// A plain number, the mantissa and the power of ten if written in scientific notation.
+    Decimal(String, Option<i32>),
+    /// Numerator and denominator, the sign is on the numerator.
+    Fraction(i64, i64),
+    Ln(u32),
+}
+
+impl NumFormat {
+    pub(crate) fn repr(&self, n: f64) -> NumRepr {
+        if !n.is_finite() {
+            return NumRepr::Decimal(n.to_string(), None);
+        }
+
+        if self.symbolic_ln {
+            if let Some(k) = (2..=MAX_LN).find(|&k| close(n, (k as f64).ln())) {
+                return NumRepr::Ln(k);
+            }
+        }
+
+        if self.fractions && n.fract() != 0.0 {
+            if let Some((p, q)) = fraction(n) {
+                return NumRepr::Fraction(p, q);
+            }
+        }
+
+        self.decimal(n)
+    }
+
+    fn decimal(&self, n: f64) -> NumRepr {
+        let Some(digits) = self.digits else {
+            let sci = format!("{n:e}");
+            let scientific = match self.notation {
+                Notation::Auto => n != 0.0 && !(-5..15).contains(&exponent(&sci)),
+                Notation::Fixed => false,
+                Notation::Scientific => true,
+            };
+            return if scientific {
+                split_exp(&sci)
+            } else {
+                NumRepr::Decimal(n.to_string(), None)
+            };
+        };
+
+        let digits = digits.max(1);
+        let sci = format!("{:.*e}", digits - 1, n);
+        let NumRepr::Decimal(mantissa, Some(exp)) = split_exp(&sci) else {
+            unreachable!()
+        };
+
+        let scientific = match self.notation {
+            Notation::Auto => !(-5..15).contains(&exp),
+            Notation::Fixed => false,
+            Notation::Scientific => true,
+        };
+
+        if scientific {
+            return NumRepr::Decimal(mantissa, Some(exp));
+        }
+
+        let decimals = (digits as i32 - 1 - exp).max(0) as usize;
+        let fixed = format!("{n:.decimals$}");
+        NumRepr::Decimal(
+            if self.notation == Notation::Auto {
+                trim_zeros(fixed)
+            } else {
+                fixed
+            },
+            None,
+        )
+    }
+}
+
+impl NumRepr {
+    /// Write the number as plain text, with `e` for the power of ten.
+    pub(crate) fn text(&self) -> String {
+        match self {
+            NumRepr::Decimal(m, None) => m.clone(),
+            NumRepr::Decimal(m, Some(e)) => format!("{m}e{e}"),
+            NumRepr::Fraction(p, q) => format!("{p}/{q}"),
+            NumRepr::Ln(k) => format!("ln {k}"),
+        }
+    }
+
+    pub(crate) fn is_negative(&self) -> bool {
+        match self {
+            NumRepr::Decimal(m, _) => m.starts_with('-'),
+            NumRepr::Fraction(p, _) => *p < 0,
+            NumRepr::Ln(_) => false,
+        }
+    }
+}
+
+fn split_exp(s: &str) -> NumRepr {
+    let (mantissa, exp) = s.split_once('e').unwrap();
+    NumRepr::Decimal(trim_zeros(mantissa.into()), Some(exp.parse().unwrap()))
+}
+
+/// The power of ten of a number written with `{:e}`.
+fn exponent(s: &str) -> i32 {
+    s.split_once('e').unwrap().1.parse().unwrap()
+}
+
+fn trim_zeros(s: String) -> String {
+    if !s.contains('.') {
+        return s;
+    }
+    s.trim_end_matches('0').trim_end_matches('.').into()
+}
+
+/// Whether `b` is `a` up to rounding, relative to the size of `a`.
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-12 * a.abs()
+}
+
+/// Find the fraction with the smallest denominator that is close to `n`, with continued
+/// fractions. `None` if that is an integer, as the number is then only close to one.
+fn fraction(n: f64) -> Option<(i64, i64)> {
+    let (mut p0, mut q0, mut p1, mut q1) = (0i64, 1i64, 1i64, 0i64);
+    let mut x = n.abs();
+
+    loop {
+        let a = x.floor();
+        if a >= i64::MAX as f64 {
+            return None;
+        }
+        let a = a as i64;
+
+        let q = a.checked_mul(q1)? + q0;
+        if q > MAX_DENOMINATOR {
+            return None;
+        }
+        (p0, q0, p1, q1) = (p1, q1, a.checked_mul(p1)?.checked_add(p0)?, q);
+        if close(n.abs(), p1 as f64 / q1 as f64) {
+            return (q1 != 1).then_some((if n < 0.0 { -p1 } else { p1 }, q1));
+        }
+
+        x = 1.0 / (x - a as f64);
+        if !x.is_finite() {
+            return None;
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::f64::consts::{FRAC_2_SQRT_PI, PI};
+use std::fmt::Debug;
+use std::sync::{Arc, OnceLock, Weak};
+
+use crate::lang::{is_ident, lex, parse_with, LangError};
+use crate::prelude::*;
+
+/// A named function of one variable defined outside of the crate, with a closure to evaluate
+/// it and its derivative as an expr of `x`.
+///
+/// Two functions are equal if they have the same name.
+pub struct Function {
+    name: String,
+    eval: Box<dyn Fn(f64) -> f64 + Send + Sync>,
+    /// The derivative, where the function calls itself with an unknown function of its name so
+    /// it does not own itself.
+    derivative: OnceLock<Expr>,
+    this: Weak<Function>,
+}
+
+impl Function {
+    /// Create a function with its derivative, which is built from the function called at `x`
+    /// so it can use the function itself, like `|f| f.clone().mul(Num(1.0).sub(f))`. An unknown
+    /// function with the same name in the derivative is the function itself.
+    pub fn new(
+        name: impl Into<String>,
+        eval: impl Fn(f64) -> f64 + Send + Sync + 'static,
+        derivative: impl FnOnce(Expr) -> Expr,
+    ) -> Arc<Self> {
+        Self::try_new(name, eval, |f| Ok::<_, Infallible>(derivative(Var.func(f))))
+            .unwrap_or_else(|e| match e {})
+    }
+
+    fn try_new<E>(
+        name: impl Into<String>,
+        eval: impl Fn(f64) -> f64 + Send + Sync + 'static,
+        derivative: impl FnOnce(&Arc<Function>) -> Result<Expr, E>,
+    ) -> Result<Arc<Self>, E> {
+        let function = Arc::new_cyclic(|this| Self {
+            name: name.into(),
+            eval: Box::new(eval),
+            derivative: OnceLock::new(),
+            this: this.clone(),
+        });
+
+        let by_name = rebuild(derivative(&function)?, &|e| match e {
+            Func(func, a) if Arc::ptr_eq(&func, &function) => a.unknown(func.name.clone(), 0),
+            e => e,
+        });
+        function.derivative.set(by_name).unwrap();
+        Ok(function)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn eval(&self, x: f64) -> f64 {
+        (self.eval)(x)
+    }
+
+    /// The derivative of the function as an expr of `x`.
+    pub fn derivative(&self) -> Expr {
+        let this = self.this.upgrade().unwrap();
+        rebuild(self.derivative.get().unwrap().clone(), &|e| match e {
+            Unknown(name, 0, a) if name == self.name => a.func(&this),
+            e => e,
+        })
+    }
+}
+
+/// Rebuild an expr with `map` applied to every node, after its args.
+fn rebuild(expr: Expr, map: &impl Fn(Expr) -> Expr) -> Expr {
+    let args = |a: Box<Expr>| Box::new(rebuild(*a, map));
+    map(match expr {
+        Bin(t, a, b) => Bin(t, args(a), args(b)),
+        Trig(t, a) => Trig(t, args(a)),
+        Derivative(n, a) => Derivative(n, args(a)),
+        Func(func, a) => Func(func, args(a)),
+        Unknown(name, n, a) => Unknown(name, n, args(a)),
+        e => e,
+    })
+}
+
+impl PartialEq for Function {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Debug for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Function").field(&self.name).finish()
+    }
+}
+
+/// A set of functions that can be used by name when parsing, like `sigmoid 2x`.
+#[derive(Clone, Debug, Default)]
+pub struct Registry {
+    functions: HashMap<String, Arc<Function>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry with `sigmoid`, `softplus` and `erf`.
+    pub fn standard() -> Self {
+        let mut registry = Self::new();
+
+        let sigmoid = Function::new(
+            "sigmoid",
+            |x| 1.0 / (1.0 + (-x).exp()),
+            |s| s.clone().mul(Num(1.0).sub(s)),
+        );
+        let softplus = Function::new("softplus", |x: f64| x.exp().ln_1p(), |_| Var.func(&sigmoid));
+        let erf = Function::new("erf", erf, |_| {
+            Num(FRAC_2_SQRT_PI).mul(Expr::E.exp(Var.exp(2.0).neg()))
+        });
+
+        registry.insert(sigmoid);
+        registry.insert(softplus);
+        registry.insert(erf);
+
+        registry
+    }
+
+    /// Add a function, replacing any function with the same name.
+    pub fn insert(&mut self, function: Arc<Function>) {
+        self.functions.insert(function.name.clone(), function);
+    }
+
+    /// Add a function with its derivative written in the language, which can use the function
+    /// itself and any function already in the registry.
+    ///
+    /// The name has to be a word that is not already a keyword.
+    pub fn register(
+        &mut self,
+        name: &str,
+        eval: impl Fn(f64) -> f64 + Send + Sync + 'static,
+        derivative: &str,
+    ) -> Result<Arc<Function>, LangError> {
+        if !is_ident(name) {
+            return Err(LangError::InvalidWord(name.into(), 0));
+        }
+
+        Function::try_new(name, eval, |function| {
+            let mut functions = self.clone();
+            functions.insert(function.clone());
+            functions.parse(derivative)
+        })
+        .inspect(|function| self.insert(function.clone()))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Arc<Function>> {
+        self.functions.get(name)
+    }
+
+    /// Parse an expr that can use the functions of this registry.
+    pub fn parse(&self, source: &str) -> Result<Expr, LangError> {
+        parse_with(lex(source)?, &HashMap::new(), self)
+    }
+}
+
+/// The error function, with its series near 0 and a continued fraction of `erfc` further away.
+fn erf(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    if x < 0.0 {
+        return -erf(-x);
+    }
+
+    if x < 2.5 {
+        let mut sum = 0.0_f64;
+        let mut term = x;
+        let mut n = 0.0;
+        while term.abs() > 1e-17 * sum.abs().max(f64::MIN_POSITIVE) {
+            sum += term / (2.0 * n + 1.0);
+            n += 1.0;
+            term *= -x * x / n;
+        }
+        return FRAC_2_SQRT_PI * sum;
+    }
+
+    let mut t = x;
+    for k in (1..=60).rev() {
+        t = x + k as f64 / 2.0 / t;
+    }
+    1.0 - (-x * x).exp() / PI.sqrt() / t
+}
@@ -0,0 +1,229 @@
+use std::f64::consts::E;
+
+use crate::{prelude::*, Polynomial};
+
+/// Integration by parts is nested at most this deep, like for `x^3 * e^x`.
+const MAX_PARTS: usize = 4;
+/// Points where an antiderivative is derived back and compared to the expr.
+const CHECKS: [f64; 8] = [-2.7, -1.3, -0.4, 0.3, 0.9, 1.7, 2.6, 4.1];
+
+impl Expr {
+    /// An antiderivative, without the constant, or `None` if none is found. This reverses the
+    /// rules of [`Expr::derive`] for the power, exponential, log and trig functions, with
+    /// linearity, u-substitution when the expr is `g(u) * u'` up to a constant, and
+    /// integration by parts for products like `x * e^x` or `x * ln x`. The antiderivative is
+    /// derived back and compared to the expr, so a wrong one is never returned.
+    pub fn integrate(&self) -> Option<Expr> {
+        let f = self.clone().expand_derivatives().simplify();
+        let integral = antiderivative(&f, MAX_PARTS)?.simplify();
+
+        let derivative = integral.clone().derive();
+        let mut checked = CHECKS
+            .iter()
+            .map(|&x| (f.evaluate(x), derivative.evaluate(x)))
+            .filter(|(a, b)| a.is_finite() && b.is_finite())
+            .peekable();
+        checked.peek()?;
+        checked
+            .all(|(a, b)| (a - b).abs() <= 1e-6 * (1.0 + a.abs()))
+            .then_some(integral)
+    }
+}
+
+impl Polynomial {
+    /// The antiderivative that is 0 at 0.
+    pub fn integrate(&self) -> Self {
+        let coefs = self.coefs().iter().enumerate();
+        Self::new(
+            std::iter::once(0.0)
+                .chain(coefs.map(|(i, c)| c / (i + 1) as f64))
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+fn antiderivative(f: &Expr, depth: usize) -> Option<Expr> {
+    if f.is_constant() {
+        return Some(f.clone().mul(Var));
+    }
+    if let Ok(poly) = Polynomial::try_from(f) {
+        return Some(poly.integrate().into());
+    }
+    if let Some((coef, power)) = monomial(f) {
+        return Some(Num(coef).mul(power_integral(power)));
+    }
+
+    let integral = |g: &Expr| antiderivative(g, depth);
+    match f {
+        Bin(Add, a, b) => return Some(integral(a)?.add(integral(b)?)),
+        Bin(Sub, a, b) => return Some(integral(a)?.sub(integral(b)?)),
+        Bin(Mul, c, g) | Bin(Mul, g, c) if c.is_constant() => {
+            return Some((**c).clone().mul(integral(g)?))
+        }
+        Bin(Div, g, c) if c.is_constant() => return Some(integral(g)?.div((**c).clone())),
+        _ => {}
+    }
+
+    table(f)
+        .or_else(|| substitution(f, &Num(1.0)))
+        .or_else(|| match f {
+            Bin(Mul, p, q) => substitution(p, q)
+                .or_else(|| substitution(q, p))
+                .or_else(|| parts(p, q, depth)),
+            Bin(Div, p, q) => {
+                let recip = Num(1.0).div((**q).clone());
+                substitution(&recip, p).or_else(|| substitution(p, &recip))
+            }
+            _ => None,
+        })
+}
+
+/// `f` as `coef * x^power` when it is a product or quotient of powers of `x`, like `x^2 / x`.
+fn monomial(f: &Expr) -> Option<(f64, f64)> {
+    match f {
+        Var => Some((1.0, 1.0)),
+        e if e.is_constant() => Some((e.evaluate(0.0), 0.0)),
+        Bin(Exp, a, n) if a.is_var() && n.is_constant() => Some((1.0, n.evaluate(0.0))),
+        Bin(Mul, a, b) => {
+            let ((ca, pa), (cb, pb)) = (monomial(a)?, monomial(b)?);
+            Some((ca * cb, pa + pb))
+        }
+        Bin(Div, a, b) => {
+            let ((ca, pa), (cb, pb)) = (monomial(a)?, monomial(b)?);
+            Some((ca / cb, pa - pb))
+        }
+        _ => None,
+    }
+}
+
+/// `x^(n+1) / (n+1)`, or `ln x` for `x^-1`.
+fn power_integral(n: f64) -> Expr {
+    if n == -1.0 {
+        Var.ln()
+    } else {
+        Num(1.0 / (n + 1.0)).mul(Var.exp(n + 1.0))
+    }
+}
+
+/// The antiderivatives of the functions of `x` that [`Expr::derive`] knows, and of the
+/// derivatives it gives like `sec x^2`.
+fn table(f: &Expr) -> Option<Expr> {
+    Some(match f {
+        Var => power_integral(1.0),
+        Bin(Exp, a, n) if a.is_var() && n.is_constant() => power_integral(n.evaluate(0.0)),
+        Bin(Div, c, x) if c.is_constant() && x.is_var() => (**c).clone().mul(Var.ln()),
+
+        // e^x -> e^x, a^x -> a^x / ln a
+        Bin(Exp, e, x) if matches!(**e, Num(E)) && x.is_var() => f.clone(),
+        Bin(Exp, a, x) if a.is_constant() && x.is_var() => f.clone().div((**a).clone().ln()),
+
+        // log_a x -> (x ln x - x) / ln a
+        Bin(Log, a, x) if a.is_constant() && x.is_var() => {
+            Var.mul(Var.ln()).sub(Var).div((**a).clone().ln())
+        }
+
+        Trig(t, x) if x.is_var() => match t {
+            Sin => Var.trig(Cos).neg(),
+            Cos => Var.trig(Sin),
+            Tan => Var.trig(Cos).ln().neg(),
+            Cot => Var.trig(Sin).ln(),
+            Sec => Var.trig(Sec).add(Var.trig(Tan)).ln(),
+            Csc => Var.trig(Csc).add(Var.trig(Cot)).ln().neg(),
+        },
+
+        // the derivatives of tan, cot, sec and csc
+        Bin(Exp, t, n) if matches!(**n, Num(2.0)) => match &**t {
+            Trig(Sec, x) if x.is_var() => Var.trig(Tan),
+            Trig(Csc, x) if x.is_var() => Var.trig(Cot).neg(),
+            _ => return None,
+        },
+        Bin(Mul, a, b) => match (&**a, &**b) {
+            (Trig(Sec, x), Trig(Tan, y)) | (Trig(Tan, y), Trig(Sec, x))
+                if x.is_var() && y.is_var() =>
+            {
+                Var.trig(Sec)
+            }
+            (Trig(Csc, x), Trig(Cot, y)) | (Trig(Cot, y), Trig(Csc, x))
+                if x.is_var() && y.is_var() =>
+            {
+                Var.trig(Csc).neg()
+            }
+            _ => return None,
+        },
+
+        _ => return None,
+    })
+}
+
+/// The ways to write `f` as `g(u)` with `g` a function of `x`, as `(g, u)`.
+fn compositions(f: &Expr) -> Vec<(Expr, Expr)> {
+    let mut found = vec![];
+    match f {
+        Trig(t, u) => found.push((Var.trig(*t), (**u).clone())),
+        Bin(Exp, a, u) if a.is_constant() => found.push(((**a).clone().exp(Var), (**u).clone())),
+        Bin(Exp, g, n) if n.is_constant() => {
+            if let Trig(t, u) = &**g {
+                found.push((Var.trig(*t).exp((**n).clone()), (**u).clone()));
+            }
+            found.push((Var.exp((**n).clone()), (**g).clone()));
+        }
+        Bin(Log, a, u) if a.is_constant() => found.push((Var.log((**a).clone()), (**u).clone())),
+        Bin(Div, c, u) if c.is_constant() => found.push(((**c).clone().div(Var), (**u).clone())),
+        Bin(Mul, a, b) => match (&**a, &**b) {
+            (Trig(s @ (Sec | Csc), u), Trig(t @ (Tan | Cot), v)) if u == v => {
+                found.push((Var.trig(*s).mul(Var.trig(*t)), (**u).clone()));
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+    // `∫ u u' = u^2 / 2`
+    found.push((Var, f.clone()));
+    found
+}
+
+/// u-substitution, `∫ g(u) * c u' = c G(u)`, with `h = g(u)` and `factor = c u'`.
+fn substitution(h: &Expr, factor: &Expr) -> Option<Expr> {
+    compositions(h).into_iter().find_map(|(g, u)| {
+        let c = constant(&factor.clone().div(u.clone().derive()))?;
+        Some(Num(c).mul(table(&g)?.compose(u)))
+    })
+}
+
+/// The value of `f` if it is the same at every check point where it is defined.
+fn constant(f: &Expr) -> Option<f64> {
+    let mut values = CHECKS
+        .iter()
+        .map(|&x| f.evaluate(x))
+        .filter(|v| v.is_finite());
+    let first = values.next()?;
+    values
+        .all(|v| (v - first).abs() <= 1e-9 * (1.0 + first.abs()))
+        .then_some(first)
+}
+
+/// Integration by parts, `∫ u dv = u v - ∫ v du`, with `u` the factor that gets simpler when
+/// derived, a log before a polynomial before anything else.
+fn parts(p: &Expr, q: &Expr, depth: usize) -> Option<Expr> {
+    if depth == 0 {
+        return None;
+    }
+
+    let rank = |e: &Expr| match e {
+        Bin(Log, ..) => 0,
+        e if Polynomial::try_from(e).is_ok() => 1,
+        _ => 2,
+    };
+    let order = if rank(q) < rank(p) {
+        [(q, p), (p, q)]
+    } else {
+        [(p, q), (q, p)]
+    };
+
+    order.into_iter().find_map(|(u, dv)| {
+        let v = antiderivative(dv, depth - 1)?.simplify();
+        let du = u.clone().derive();
+        let rest = antiderivative(&v.clone().mul(du).simplify(), depth - 1)?;
+        Some(u.clone().mul(v).sub(rest))
+    })
+}
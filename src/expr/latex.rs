@@ -0,0 +1,173 @@
+use std::f64::consts::E;
+use std::fmt::{Display, Formatter, Result};
+
+use super::format::{NumFormat, NumRepr};
+use crate::{
+    Bin::*,
+    Expr::{self, *},
+    Trig::*,
+};
+
+/// A LaTeX rendering of an [`Expr`], created by [`Expr::latex`].
+pub struct Latex<'a> {
+    expr: &'a Expr,
+    format: NumFormat,
+}
+
+impl Expr {
+    /// Write this expr as LaTeX math, without the surrounding `$`.
+    pub fn latex(&self) -> Latex<'_> {
+        self.latex_with(NumFormat::default())
+    }
+
+    /// Write this expr as LaTeX math with numbers written with `format`.
+    pub fn latex_with(&self, format: NumFormat) -> Latex<'_> {
+        Latex { expr: self, format }
+    }
+}
+
+impl Display for Latex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.write_expr(self.expr, f)
+    }
+}
+
+impl Latex<'_> {
+    fn write_expr(&self, expr: &Expr, f: &mut Formatter<'_>) -> Result {
+        match expr {
+            Bin(t @ (Add | Sub), a, b) => {
+                self.write_paren(a, f, false)?;
+                write!(f, "{}", if *t == Add { " + " } else { " - " })?;
+                let paren = self.precedence(b) < 1
+                    || (*t == Sub && self.precedence(b) == 1)
+                    || self.is_negative(b);
+                self.write_paren(b, f, paren)
+            }
+            Bin(Mul, a, b) => {
+                self.write_paren(a, f, self.precedence(a) < 2)?;
+                write!(f, " \\cdot ")?;
+                self.write_paren(b, f, self.precedence(b) < 2 || self.is_negative(b))
+            }
+            Bin(Div, a, b) => {
+                write!(f, "\\frac{{")?;
+                self.write_expr(a, f)?;
+                write!(f, "}}{{")?;
+                self.write_expr(b, f)?;
+                write!(f, "}}")
+            }
+
+            Bin(Exp, a, b) if **b == Num(0.5) => {
+                write!(f, "\\sqrt{{")?;
+                self.write_expr(a, f)?;
+                write!(f, "}}")
+            }
+            Bin(Exp, a, b) => {
+                self.write_paren(a, f, !self.is_atom(a))?;
+                write!(f, "^{{")?;
+                self.write_expr(b, f)?;
+                write!(f, "}}")
+            }
+
+            Bin(Log, a, b) if **a == Num(E) => {
+                write!(f, "\\ln")?;
+                self.write_arg(b, f)
+            }
+            Bin(Log, a, b) => {
+                write!(f, "\\log_{{")?;
+                self.write_expr(a, f)?;
+                write!(f, "}}")?;
+                self.write_arg(b, f)
+            }
+
+            Trig(t, a) => {
+                let name = match t {
+                    Sin => "\\sin",
+                    Cos => "\\cos",
+                    Tan => "\\tan",
+                    Csc => "\\csc",
+                    Sec => "\\sec",
+                    Cot => "\\cot",
+                };
+                write!(f, "{name}")?;
+                self.write_arg(a, f)
+            }
+
+            Func(func, a) => {
+                write!(f, "\\operatorname{{{}}}", func.name())?;
+                self.write_arg(a, f)
+            }
+
+            Unknown(name, n, a) => {
+                write!(f, "{name}{}", "'".repeat(*n))?;
+                self.write_paren(a, f, true)
+            }
+
+            Derivative(1, a) => {
+                write!(f, "\\frac{{d}}{{dx}}")?;
+                self.write_arg(a, f)
+            }
+            Derivative(n, a) => {
+                write!(f, "\\frac{{d^{{{n}}}}}{{dx^{{{n}}}}}")?;
+                self.write_arg(a, f)
+            }
+
+            Param(name) => write!(f, "{name}"),
+            Var => write!(f, "x"),
+            Num(E) => write!(f, "e"),
+            Num(n) => match self.format.repr(*n) {
+                NumRepr::Decimal(m, None) => write!(f, "{m}"),
+                NumRepr::Decimal(m, Some(e)) => write!(f, "{m} \\times 10^{{{e}}}"),
+                NumRepr::Fraction(p, q) if p < 0 => write!(f, "-\\frac{{{}}}{{{q}}}", -p),
+                NumRepr::Fraction(p, q) => write!(f, "\\frac{{{p}}}{{{q}}}"),
+                NumRepr::Ln(k) => write!(f, "\\ln {k}"),
+            },
+        }
+    }
+
+    fn write_paren(&self, expr: &Expr, f: &mut Formatter<'_>, paren: bool) -> Result {
+        if paren {
+            write!(f, "\\left(")?;
+            self.write_expr(expr, f)?;
+            write!(f, "\\right)")
+        } else {
+            self.write_expr(expr, f)
+        }
+    }
+
+    /// Write the argument of a function, a unit is separated by a space and anything else is
+    /// wrapped in parentheses.
+    fn write_arg(&self, expr: &Expr, f: &mut Formatter<'_>) -> Result {
+        if self.is_atom(expr) {
+            write!(f, " ")?;
+            self.write_expr(expr, f)
+        } else {
+            self.write_paren(expr, f, true)
+        }
+    }
+
+    fn is_atom(&self, expr: &Expr) -> bool {
+        match expr {
+            Var | Num(E) => true,
+            Num(n) => matches!(self.format.repr(*n), NumRepr::Decimal(_, None)) && *n >= 0.0,
+            _ => false,
+        }
+    }
+
+    fn is_negative(&self, expr: &Expr) -> bool {
+        matches!(expr, Num(n) if self.format.repr(*n).is_negative())
+    }
+
+    /// The precedence of an expr as it is written, a fraction does not need parentheses.
+    fn precedence(&self, expr: &Expr) -> usize {
+        match expr {
+            Num(n) if *n != E => match self.format.repr(*n) {
+                NumRepr::Decimal(_, None) => expr.precedence(),
+                NumRepr::Decimal(..) => Mul.precedence(),
+                NumRepr::Fraction(..) => Exp.precedence(),
+                NumRepr::Ln(_) => Log.precedence(),
+            },
+            Bin(Div, ..) => Exp.precedence(),
+            _ => expr.precedence(),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use std::cell::Cell;
+use std::cmp::Ordering;
+
+use crate::prelude::*;
+
+/// L'Hôpital's rule is applied at most this many times for a whole limit, as the quotients
+/// it gives can hold more indeterminate quotients.
+const MAX_LHOPITAL: usize = 8;
+/// The order of the Taylor series used when L'Hôpital's rule gives up.
+const SERIES_ORDER: usize = 12;
+/// A Taylor coefficient smaller than this is taken as 0, as it is found with rounding errors.
+const ZERO: f64 = 1e-12;
+/// A value is taken as 0 when it is this many times smaller than the values it is computed
+/// from, so `sin π` is.
+const ROUNDING: f64 = 8.0 * f64::EPSILON;
+/// A value larger than this at the point is checked for a pole, as `tan (π/2)` is finite in
+/// floats.
+const HUGE: f64 = 1e12;
+
+/// The side a limit approaches its point from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// From below.
+    Left,
+    /// From above.
+    Right,
+    /// From both sides, the limit exists only if they agree.
+    Both,
+}
+
+/// The result of [`Expr::limit`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Limit {
+    Finite(f64),
+    PosInfinity,
+    NegInfinity,
+    DoesNotExist,
+}
+
+impl From<Option<f64>> for Limit {
+    fn from(value: Option<f64>) -> Self {
+        match value {
+            Some(f64::INFINITY) => Limit::PosInfinity,
+            Some(f64::NEG_INFINITY) => Limit::NegInfinity,
+            Some(v) if v.is_finite() => Limit::Finite(v),
+            _ => Limit::DoesNotExist,
+        }
+    }
+}
+
+impl Expr {
+    /// The limit of the expr at `point`, which can be infinite, where the direction is then
+    /// ignored. A quotient that gives `0/0` or `∞/∞` is solved with L'Hôpital's rule, and
+    /// with the Taylor series when the rule does not end. `0 * ∞`, `∞ - ∞`, `0^0`, `1^∞` and
+    /// `∞^0` are rewritten to a quotient or approached numerically, so `x ln x` works too.
+    pub fn limit(&self, point: f64, direction: Direction) -> Limit {
+        let f = self.clone().expand_derivatives().simplify();
+        let side = |side| {
+            Approach {
+                point,
+                side,
+                steps: Cell::new(0),
+            }
+            .limit(&f)
+        };
+
+        if point.is_infinite() {
+            return side(-point.signum()).into();
+        }
+        match direction {
+            Direction::Left => side(-1.0).into(),
+            Direction::Right => side(1.0).into(),
+            Direction::Both => match (side(-1.0), side(1.0)) {
+                (Some(l), Some(r)) if same(l, r) => Some(r).into(),
+                _ => Limit::DoesNotExist,
+            },
+        }
+    }
+}
+
+fn same(a: f64, b: f64) -> bool {
+    a == b
+        || (a.is_finite() && b.is_finite() && (a - b).abs() <= 1e-9 * (1.0 + a.abs().max(b.abs())))
+}
+
+/// `v` computed from values as large as `scale`, or 0 if it is only a rounding error of them.
+fn snap(v: f64, scale: f64) -> f64 {
+    if v.is_finite() && v.abs() <= ROUNDING * scale.abs() {
+        0.0
+    } else {
+        v
+    }
+}
+
+/// The quotient of two exprs, with the quotients they are made of moved out, so
+/// `(1 / x) / (-1 / x^2)` is `x^2 / (x * -1)` which L'Hôpital's rule can end.
+fn fraction(n: Expr, d: Expr) -> Expr {
+    match (n, d) {
+        (Bin(Div, a, b), Bin(Div, c, d)) => a.mul(*d).div(b.mul(*c)),
+        (Bin(Div, a, b), d) => a.div(b.mul(d)),
+        (n, Bin(Div, c, d)) => n.mul(*d).div(*c),
+        (n, d) => n.div(d),
+    }
+}
+
+/// A point approached from one side, `side` is -1 from below and 1 from above.
+struct Approach {
+    point: f64,
+    side: f64,
+    /// The times L'Hôpital's rule was applied.
+    steps: Cell<usize>,
+}
+
+impl Approach {
+    /// The limit of `f`, `None` if it does not exist. Infinite limits are the float
+    /// infinities.
+    fn limit(&self, f: &Expr) -> Option<f64> {
+        let Bin(t @ (Add | Sub | Mul | Div | Exp), p, q) = f else {
+            // elementary functions are continuous, an infinity like `ln 0` is the limit when
+            // the values next to it have its sign, a huge value may be a pole on either side
+            let mut v = f.evaluate(self.point);
+            if let Trig(_, a) = f {
+                v = snap(v, a.evaluate(self.point));
+            }
+            return if v.abs() < HUGE || (v.is_infinite() && self.sign(f) == Some(v.signum())) {
+                Some(v)
+            } else {
+                self.numeric(f)
+            };
+        };
+        let (Some(lp), Some(lq)) = (self.limit(p), self.limit(q)) else {
+            // like `x * sin(1/x)`, where a part alone has no limit
+            return self.numeric(f);
+        };
+        let (p, q) = (p.as_ref().clone(), q.as_ref().clone());
+
+        match t {
+            Div if (lp == 0.0 && lq == 0.0) || (lp.is_infinite() && lq.is_infinite()) => {
+                self.lhopital(p, q)
+            }
+            Div if lq == 0.0 => Some(f64::INFINITY.copysign(lp * self.sign(&q)?)),
+            Div => Some(lp / lq),
+            Mul => self.product(p, q, lp, lq).or_else(|| self.numeric(f)),
+            // b^e is e^(e ln b)
+            Exp if (lp == 0.0 && lq == 0.0)
+                || (lp == 1.0 && lq.is_infinite())
+                || (lp.is_infinite() && lq == 0.0) =>
+            {
+                self.product(q, p.ln(), lq, lp.ln())
+                    .map(f64::exp)
+                    .or_else(|| self.numeric(f))
+            }
+            _ => {
+                let v = match t {
+                    Add => snap(lp + lq, lp.abs().max(lq.abs())),
+                    Sub => snap(lp - lq, lp.abs().max(lq.abs())),
+                    _ => lp.powf(lq),
+                };
+                // ∞ - ∞, or a power like `x^-1` where the sign of the infinity is not known
+                if v.is_nan() || (*t == Exp && lp == 0.0 && v.is_infinite()) {
+                    self.numeric(f)
+                } else {
+                    Some(v)
+                }
+            }
+        }
+    }
+
+    /// The limit of `p * q` from the limits of its factors. `0 * ∞` is the quotient
+    /// `0 / (1 / ∞)`, or `∞ / (1 / 0)` when L'Hôpital's rule does not solve that one, like
+    /// for `x ln x`.
+    fn product(&self, p: Expr, q: Expr, lp: f64, lq: f64) -> Option<f64> {
+        let (zero, infinity) = if lp == 0.0 && lq.is_infinite() {
+            (p, q)
+        } else if lp.is_infinite() && lq == 0.0 {
+            (q, p)
+        } else {
+            return Some(lp * lq);
+        };
+
+        let steps = self.steps.get();
+        self.lhopital(zero.clone(), Num(1.0).div(infinity.clone()))
+            .or_else(|| {
+                self.steps.set(steps);
+                self.lhopital(infinity, Num(1.0).div(zero))
+            })
+    }
+
+    /// The limit of `n / d` with L'Hôpital's rule, or with the series of the first quotient
+    /// when the rule gives up, as the quotients only grow.
+    fn lhopital(&self, n: Expr, d: Expr) -> Option<f64> {
+        let steps = self.steps.get();
+        if steps >= MAX_LHOPITAL {
+            return None;
+        }
+
+        self.steps.set(steps + 1);
+        let quotient = fraction(n.clone().derive(), d.clone().derive()).simplify();
+        let limit = self.limit(&quotient);
+        if steps == 0 {
+            limit.or_else(|| self.series(n, d))
+        } else {
+            limit
+        }
+    }
+
+    /// The limit of `n / d` from the lowest terms of their Taylor series.
+    fn series(&self, n: Expr, d: Expr) -> Option<f64> {
+        if self.point.is_infinite() {
+            return self.numeric(&n.div(d));
+        }
+
+        // the first coefficient that is not 0, `Err` if the series does not exist
+        let lowest = |e: &Expr| {
+            for (k, c) in e
+                .taylor_coefs(self.point)
+                .take(SERIES_ORDER + 1)
+                .enumerate()
+            {
+                if c.is_nan() {
+                    return Err(());
+                }
+                if c.abs() > ZERO {
+                    return Ok(Some((k, c)));
+                }
+            }
+            Ok(None)
+        };
+        match (lowest(&n), lowest(&d)) {
+            (Ok(None), Ok(Some(_))) => Some(0.0),
+            (Ok(Some((kn, cn))), Ok(Some((kd, cd)))) => Some(match kn.cmp(&kd) {
+                Ordering::Greater => 0.0,
+                Ordering::Equal => cn / cd,
+                Ordering::Less => {
+                    let sign = if (kd - kn) % 2 == 0 { 1.0 } else { self.side };
+                    f64::INFINITY.copysign(cn / cd * sign)
+                }
+            }),
+            _ => self.numeric(&n.div(d)),
+        }
+    }
+
+    /// A point next to the approached one, closer as `k` grows. An infinite point is
+    /// approached with larger and larger numbers.
+    fn near(&self, k: i32) -> f64 {
+        let a = self.point;
+        if a.is_infinite() {
+            a.signum() * 10f64.powi(k)
+        } else {
+            a + self.side * 10f64.powi(-k) * (1.0 + a.abs())
+        }
+    }
+
+    /// The sign of `f` next to the point, `None` if it changes or is not known.
+    fn sign(&self, f: &Expr) -> Option<f64> {
+        let signs: Vec<_> = (6..=9).map(|k| f.evaluate(self.near(k)).signum()).collect();
+        if signs.iter().all(|&sign| sign == signs[0]) && !signs[0].is_nan() {
+            Some(signs[0])
+        } else {
+            None
+        }
+    }
+
+    /// The limit from the values of `f` closer and closer to the point, for what the rules do
+    /// not solve. A limit found this way is only as precise as the last values, so it is 0
+    /// when it is smaller than their difference, like for `x sin(1/x)`.
+    fn numeric(&self, f: &Expr) -> Option<f64> {
+        let values: Vec<_> = (5..=8).map(|k| f.evaluate(self.near(k))).collect();
+        if values.iter().any(|v| v.is_nan()) {
+            return None;
+        }
+
+        let growing = values
+            .windows(2)
+            .all(|w| w[1].abs() > w[0].abs() && w[0] * w[1] > 0.0);
+        let (v, prev) = (values[3], values[2]);
+        if v.is_infinite() || (growing && v.abs() > 1e6) {
+            Some(f64::INFINITY.copysign(v))
+        } else if (v - prev).abs() <= 1e-6 * (1.0 + v.abs()) {
+            Some(if v.abs() <= (v - prev).abs() { 0.0 } else { v })
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::sync::{Arc, LazyLock};
+
+use crate::{
+    prelude::*,
+    rewrite::{Condition, Rewriter, Rule},
+};
+
+/// What can be assumed about the exprs under a log or raised to a power.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Domain {
+    /// Only use the laws that are true for every real `x`, like `ln(e^x) = x`.
+    #[default]
+    Real,
+    /// Every log argument and every base is positive, so `e^(ln x) = x`, `ln(x^2) = 2 ln x` and
+    /// `(x^a)^b = x^(a b)`.
+    Positive,
+}
+
+/// Laws true for every real.
+const REAL: &str = "
+ln (e^?a) -> ?a
+ln (?b^?a) -> ?a * ln ?b if ?b is positive
+?c * ?a / ?c -> ?a if ?c is nonzero
+
+(?a^?m)^?n -> ?a^(?m * ?n) if ?n is int
+?a^?m * ?a^?n -> ?a^(?m + ?n) if ?a is positive
+?a^?m * ?a^?n -> ?a^(?m + ?n) if ?m is int and ?n is int
+?a^?n * ?a -> ?a^(?n + 1) if ?n is int
+?a * ?a^?n -> ?a^(?n + 1) if ?n is int
+";
+
+/// Laws that need the logs and bases to be positive.
+const POSITIVE: &str = "
+e^(ln ?a) -> ?a
+e^(?a * ln ?b) -> ?b^?a
+e^(ln ?b * ?a) -> ?b^?a
+
+ln (?a^?b) -> ?b * ln ?a
+ln (?a * ?b) -> ln ?a + ln ?b
+ln (?a / ?b) -> ln ?a - ln ?b
+
+(?a^?m)^?n -> ?a^(?m * ?n)
+?a^?m * ?a^?n -> ?a^(?m + ?n)
+?a^?n * ?a -> ?a^(?n + 1)
+?a * ?a^?n -> ?a^(?n + 1)
+";
+
+/// `log_a b -> ln b / ln a`, when `a` is not already `e`.
+fn change_base() -> Rule {
+    Rule::new(
+        "log_?a ?b".parse().unwrap(),
+        "ln ?b / ln ?a".parse().unwrap(),
+    )
+    .when(Condition::Custom(Arc::new(|b| b["a"] != Expr::E)))
+}
+
+/// The change of base then the rules of every source.
+fn rules(sources: &[&str]) -> Rewriter {
+    let mut rewriter = Rewriter::new();
+    rewriter.add(change_base());
+    for source in sources {
+        for rule in Rewriter::parse(source).unwrap().rules() {
+            rewriter.add(rule.clone());
+        }
+    }
+    rewriter
+}
+
+static REAL_RULES: LazyLock<Rewriter> = LazyLock::new(|| rules(&[REAL]));
+static POSITIVE_RULES: LazyLock<Rewriter> = LazyLock::new(|| rules(&[REAL, POSITIVE]));
+
+impl Expr {
+    /// Write every log as `ln`, then apply the laws of logs and powers that are true in the
+    /// domain and [`Expr::simplify`] until the expr does not change.
+    pub fn simplify_logs(self, domain: Domain) -> Self {
+        match domain {
+            Domain::Real => REAL_RULES.rewrite_simplify(self),
+            Domain::Positive => POSITIVE_RULES.rewrite_simplify(self),
+        }
+    }
+}
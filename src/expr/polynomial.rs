@@ -0,0 +1,269 @@
+use std::fmt::Display;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::prelude::*;
+
+/// Coefficients smaller than this, relative to the largest one, are taken as 0 by
+/// [`Polynomial::gcd`].
+const GCD_TOLERANCE: f64 = 1e-9;
+/// An expr of a higher degree is not converted to a polynomial, as its powers take long to
+/// multiply out and its coefficients overflow anyway.
+const MAX_DEGREE: usize = 1000;
+
+/// A polynomial of `x` stored as its coefficients, the constant first. The last coefficient is
+/// never 0, so the zero polynomial has no coefficient.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polynomial {
+    coefs: Vec<f64>,
+}
+
+/// The error of converting an expr that is not a polynomial of `x`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct NotPolynomial;
+
+impl Display for NotPolynomial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the expression is not a polynomial")
+    }
+}
+
+impl std::error::Error for NotPolynomial {}
+
+impl Polynomial {
+    /// A polynomial from its coefficients, the constant first.
+    pub fn new(coefs: impl Into<Vec<f64>>) -> Self {
+        let mut coefs = coefs.into();
+        while coefs.last() == Some(&0.0) {
+            coefs.pop();
+        }
+        Self { coefs }
+    }
+
+    pub fn coefs(&self) -> &[f64] {
+        &self.coefs
+    }
+
+    /// The degree of the polynomial, `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefs.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefs.is_empty()
+    }
+
+    /// Evaluate with Horner's method.
+    pub fn evaluate(&self, x: f64) -> f64 {
+        self.coefs.iter().rev().fold(0.0, |acc, c| acc * x + c)
+    }
+
+    pub fn derive(&self) -> Self {
+        Self::new(
+            self.coefs
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, c)| i as f64 * c)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// The quotient and the remainder of the division by `divisor`.
+    ///
+    /// # Panics
+    ///
+    /// If `divisor` is the zero polynomial.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let Some(n) = divisor.degree() else {
+            panic!("division by the zero polynomial");
+        };
+        if self.coefs.len() <= n {
+            return (Self::default(), self.clone());
+        }
+
+        let lead = divisor.coefs[n];
+        let mut rem = self.coefs.clone();
+        let mut quot = vec![0.0; rem.len() - n];
+        for i in (0..quot.len()).rev() {
+            let c = rem[i + n] / lead;
+            quot[i] = c;
+            for (j, d) in divisor.coefs.iter().enumerate() {
+                rem[i + j] -= c * d;
+            }
+            rem[i + n] = 0.0;
+        }
+        rem.truncate(n);
+
+        (Self::new(quot), Self::new(rem))
+    }
+
+    /// The monic greatest common divisor, computed with Euclid's algorithm. Coefficients are
+    /// floats, so a remainder that is almost 0 is taken as 0.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let (_, r) = a.div_rem(&b);
+            let scale = a.coefs.iter().fold(0.0_f64, |m, c| m.max(c.abs()));
+            a = b;
+            b = if r.coefs.iter().all(|c| c.abs() <= GCD_TOLERANCE * scale) {
+                Self::default()
+            } else {
+                r
+            };
+        }
+
+        match a.coefs.last() {
+            Some(&lead) => Self::new(a.coefs.iter().map(|c| c / lead).collect::<Vec<_>>()),
+            None => a,
+        }
+    }
+
+    fn constant(c: f64) -> Self {
+        Self::new(vec![c])
+    }
+
+    fn scale(self, k: f64) -> Self {
+        Self::new(self.coefs.into_iter().map(|c| c * k).collect::<Vec<_>>())
+    }
+
+    /// Raise to a power by squaring.
+    fn powi(&self, mut n: usize) -> Self {
+        let (mut base, mut acc) = (self.clone(), Self::constant(1.0));
+        while n > 0 {
+            if n % 2 == 1 {
+                acc = acc * base.clone();
+            }
+            n /= 2;
+            if n > 0 {
+                base = base.clone() * base;
+            }
+        }
+        acc
+    }
+
+    fn capped(self) -> Result<Self, NotPolynomial> {
+        match self.degree() {
+            Some(d) if d > MAX_DEGREE => Err(NotPolynomial),
+            _ => Ok(self),
+        }
+    }
+}
+
+impl Add for Polynomial {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let (mut long, short) = if self.coefs.len() >= rhs.coefs.len() {
+            (self.coefs, rhs.coefs)
+        } else {
+            (rhs.coefs, self.coefs)
+        };
+        for (a, b) in long.iter_mut().zip(short) {
+            *a += b;
+        }
+        Self::new(long)
+    }
+}
+
+impl Neg for Polynomial {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.scale(-1.0)
+    }
+}
+
+impl Sub for Polynomial {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Mul for Polynomial {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        if self.is_zero() || rhs.is_zero() {
+            return Self::default();
+        }
+
+        let mut coefs = vec![0.0; self.coefs.len() + rhs.coefs.len() - 1];
+        for (i, a) in self.coefs.iter().enumerate() {
+            for (j, b) in rhs.coefs.iter().enumerate() {
+                coefs[i + j] += a * b;
+            }
+        }
+        Self::new(coefs)
+    }
+}
+
+impl TryFrom<&Expr> for Polynomial {
+    type Error = NotPolynomial;
+
+    /// Any part that does not depend on `x` is evaluated, so `ln 2 * x` is a polynomial. An
+    /// expr of a degree above 1000 is not one.
+    fn try_from(expr: &Expr) -> Result<Self, Self::Error> {
+        if expr.is_constant() {
+            return Ok(Self::constant(expr.evaluate(0.0)));
+        }
+
+        let poly = |e: &Expr| Polynomial::try_from(e);
+        Ok(match expr {
+            Var => Self::new(vec![0.0, 1.0]),
+            Bin(Add, a, b) => poly(a)? + poly(b)?,
+            Bin(Sub, a, b) => poly(a)? - poly(b)?,
+            Bin(Mul, a, b) => (poly(a)? * poly(b)?).capped()?,
+            Bin(Div, a, b) if b.is_constant() => poly(a)?.scale(1.0 / b.evaluate(0.0)),
+            Bin(Exp, a, b) if b.is_constant() => {
+                let n = b.evaluate(0.0);
+                let a = poly(a)?;
+                let degree = a.degree().unwrap_or(0) as f64 * n;
+                if n < 0.0 || n.fract() != 0.0 || degree > MAX_DEGREE as f64 {
+                    return Err(NotPolynomial);
+                }
+                a.powi(n as usize)
+            }
+            Derivative(n, a) => (0..*n).fold(poly(a)?, |p, _| p.derive()),
+            _ => return Err(NotPolynomial),
+        })
+    }
+}
+
+impl TryFrom<Expr> for Polynomial {
+    type Error = NotPolynomial;
+
+    fn try_from(expr: Expr) -> Result<Self, Self::Error> {
+        Self::try_from(&expr)
+    }
+}
+
+impl From<&Polynomial> for Expr {
+    /// The highest power first, like `3 * x^2 + 2 * x + 1`.
+    fn from(poly: &Polynomial) -> Self {
+        poly.coefs
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| Num(c).mul(Var.exp(i)))
+            .fold(Num(0.0), Expr::add)
+            .collect()
+    }
+}
+
+impl From<Polynomial> for Expr {
+    fn from(poly: Polynomial) -> Self {
+        Self::from(&poly)
+    }
+}
+
+impl Expr {
+    /// Derive the expr, as a [`Polynomial`] if it is one, which gives back the collected
+    /// derivative like `3 * x^2 + 2 * x + 1` instead of a tree of product rules.
+    pub fn derive_fast(self) -> Self {
+        match Polynomial::try_from(&self) {
+            Ok(poly) => poly.derive().into(),
+            Err(_) => self.derive(),
+        }
+    }
+}
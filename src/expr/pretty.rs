@@ -0,0 +1,362 @@
+use std::f64::consts::E;
+use std::fmt::Display;
+
+use super::format::{NumFormat, NumRepr};
+use crate::{
+    Bin::*,
+    Expr::{self, *},
+    Trig::*,
+};
+
+/// A 2D unicode rendering of an [`Expr`], created by [`Expr::pretty`].
+///
+/// Fractions are laid out vertically, exponents are written as superscripts and log bases as
+/// subscripts when the characters allow it, otherwise they are raised or lowered a line.
+pub struct Pretty<'a> {
+    expr: &'a Expr,
+    format: NumFormat,
+}
+
+impl Expr {
+    /// Render this expr as multi-line unicode text, meant for terminal output.
+    pub fn pretty(&self) -> Pretty<'_> {
+        self.pretty_with(NumFormat::default())
+    }
+
+    /// Render this expr as multi-line unicode text with numbers written with `format`.
+    pub fn pretty_with(&self, format: NumFormat) -> Pretty<'_> {
+        Pretty { expr: self, format }
+    }
+}
+
+impl Display for Pretty<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let block = render(self.expr, &self.format);
+        for (i, line) in block.lines.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+/// A rectangle of text. Every line has the same width and `baseline` is the line other blocks
+/// line up with when put side by side.
+#[derive(Clone, Debug)]
+struct Block {
+    lines: Vec<String>,
+    baseline: usize,
+}
+
+impl Block {
+    fn text(s: impl Into<String>) -> Self {
+        Self {
+            lines: vec![s.into()],
+            baseline: 0,
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.lines[0].chars().count()
+    }
+
+    fn height(&self) -> usize {
+        self.lines.len()
+    }
+
+    fn is_line(&self) -> bool {
+        self.height() == 1
+    }
+
+    /// Put another block on the right of this one, lining up the baselines.
+    fn beside(self, other: Block) -> Self {
+        let baseline = self.baseline.max(other.baseline);
+        let below = (self.height() - self.baseline).max(other.height() - other.baseline);
+
+        let a = self.pad(baseline, below);
+        let b = other.pad(baseline, below);
+
+        Self {
+            lines: a
+                .lines
+                .into_iter()
+                .zip(b.lines)
+                .map(|(a, b)| a + &b)
+                .collect(),
+            baseline,
+        }
+    }
+
+    /// Add empty lines so that there are `above` lines over the baseline and `below` lines from
+    /// the baseline down.
+    fn pad(self, above: usize, below: usize) -> Self {
+        let blank = " ".repeat(self.width());
+        let top = above - self.baseline;
+        let bottom = below - (self.height() - self.baseline);
+
+        let mut lines = vec![blank.clone(); top];
+        lines.extend(self.lines);
+        lines.extend(vec![blank; bottom]);
+
+        Self {
+            lines,
+            baseline: above,
+        }
+    }
+
+    /// Stack a numerator over a denominator with a bar in between.
+    fn fraction(num: Block, den: Block) -> Self {
+        let width = num.width().max(den.width()) + 2;
+        let baseline = num.height();
+
+        let mut lines = num.center(width).lines;
+        lines.push("─".repeat(width));
+        lines.extend(den.center(width).lines);
+
+        Self { lines, baseline }
+    }
+
+    fn center(self, width: usize) -> Self {
+        let left = (width - self.width()) / 2;
+        let right = width - self.width() - left;
+
+        Self {
+            lines: self
+                .lines
+                .into_iter()
+                .map(|l| format!("{}{l}{}", " ".repeat(left), " ".repeat(right)))
+                .collect(),
+            baseline: self.baseline,
+        }
+    }
+
+    fn paren(self) -> Self {
+        if self.is_line() {
+            return Block::text(format!("({})", self.lines[0]));
+        }
+
+        let last = self.height() - 1;
+        let (open, close): (Vec<_>, Vec<_>) = (0..self.height())
+            .map(|i| match i {
+                0 => ("⎛", "⎞"),
+                i if i == last => ("⎝", "⎠"),
+                _ => ("⎜", "⎟"),
+            })
+            .unzip();
+
+        Self {
+            lines: self
+                .lines
+                .into_iter()
+                .enumerate()
+                .map(|(i, l)| format!("{}{l}{}", open[i], close[i]))
+                .collect(),
+            baseline: self.baseline,
+        }
+    }
+
+    /// Attach `script` to the top right of this block, as a superscript if possible.
+    fn raise(mut self, script: Block) -> Self {
+        if let Some(sup) = script.map_line(superscript) {
+            self.lines[0].push_str(&sup);
+            let pad = " ".repeat(sup.chars().count());
+            self.lines[1..].iter_mut().for_each(|l| l.push_str(&pad));
+            return self;
+        }
+
+        let width = self.width();
+        let script_width = script.width();
+        let mut lines: Vec<_> = script
+            .lines
+            .into_iter()
+            .map(|l| " ".repeat(width) + &l)
+            .collect();
+        let baseline = lines.len() + self.baseline;
+        lines.extend(
+            self.lines
+                .into_iter()
+                .map(|l| l + &" ".repeat(script_width)),
+        );
+
+        Self { lines, baseline }
+    }
+
+    /// Attach `script` to the bottom right of this single line block, as a subscript if
+    /// possible.
+    fn lower(self, script: Block) -> Self {
+        if let Some(sub) = script.map_line(subscript) {
+            return Block::text(self.lines[0].clone() + &sub);
+        }
+
+        let width = self.width();
+        let script_width = script.width();
+        let mut lines: Vec<_> = self
+            .lines
+            .into_iter()
+            .map(|l| l + &" ".repeat(script_width))
+            .collect();
+        lines.extend(script.lines.into_iter().map(|l| " ".repeat(width) + &l));
+
+        Self { lines, baseline: 0 }
+    }
+
+    /// Map every character of a single line block, failing if it is not a single line or any
+    /// character cannot be mapped.
+    fn map_line(&self, map: fn(char) -> Option<char>) -> Option<String> {
+        if !self.is_line() {
+            return None;
+        }
+        self.lines[0].chars().map(map).collect()
+    }
+}
+
+fn superscript(c: char) -> Option<char> {
+    Some(match c {
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        '9' => '⁹',
+        '+' => '⁺',
+        '-' => '⁻',
+        '(' => '⁽',
+        ')' => '⁾',
+        'x' => 'ˣ',
+        'e' => 'ᵉ',
+        '·' => '·',
+        _ => return None,
+    })
+}
+
+fn subscript(c: char) -> Option<char> {
+    Some(match c {
+        '0' => '₀',
+        '1' => '₁',
+        '2' => '₂',
+        '3' => '₃',
+        '4' => '₄',
+        '5' => '₅',
+        '6' => '₆',
+        '7' => '₇',
+        '8' => '₈',
+        '9' => '₉',
+        '+' => '₊',
+        '-' => '₋',
+        '(' => '₍',
+        ')' => '₎',
+        'x' => 'ₓ',
+        'e' => 'ₑ',
+        _ => return None,
+    })
+}
+
+fn render(expr: &Expr, format: &NumFormat) -> Block {
+    match expr {
+        Bin(t @ (Add | Sub), a, b) => {
+            let op = if *t == Add { " + " } else { " - " };
+            let b_paren = b.precedence() < 1 || (*t == Sub && b.precedence() == 1) || is_neg(b);
+            render_paren(a, a.precedence() < 1, format)
+                .beside(Block::text(op))
+                .beside(render_paren(b, b_paren, format))
+        }
+        Bin(Mul, a, b) => render_paren(a, a.precedence() < 2, format)
+            .beside(Block::text("·"))
+            .beside(render_paren(b, b.precedence() < 2 || is_neg(b), format)),
+        Bin(Div, a, b) => Block::fraction(render(a, format), render(b, format)),
+
+        Bin(Exp, a, b) if **b == Num(0.5) => {
+            Block::text("√").beside(render_paren(a, !is_plain(a, format), format))
+        }
+        Bin(Exp, a, b) => render_paren(a, !is_plain(a, format), format).raise(render(b, format)),
+
+        Bin(Log, a, b) if **a == Num(E) => Block::text("ln").beside(render_arg(b, format)),
+        Bin(Log, a, b) => Block::text("log")
+            .lower(render(a, format))
+            .beside(render_arg(b, format)),
+
+        Trig(t, a) => Block::text(match t {
+            Sin => "sin",
+            Cos => "cos",
+            Tan => "tan",
+            Csc => "csc",
+            Sec => "sec",
+            Cot => "cot",
+        })
+        .beside(render_arg(a, format)),
+
+        Func(func, a) => Block::text(func.name()).beside(render_arg(a, format)),
+
+        Unknown(name, n, a) => {
+            Block::text(format!("{name}{}", "′".repeat(*n))).beside(render(a, format).paren())
+        }
+
+        Derivative(1, a) => {
+            Block::fraction(Block::text("d"), Block::text("dx")).beside(render_arg(a, format))
+        }
+        Derivative(n, a) => {
+            let order = || Block::text(n.to_string());
+            Block::fraction(
+                Block::text("d").raise(order()),
+                Block::text("dx").raise(order()),
+            )
+            .beside(render_arg(a, format))
+        }
+
+        Param(name) => Block::text(name.clone()),
+        Var => Block::text("x"),
+        Num(E) => Block::text("e"),
+        Num(n) => match format.repr(*n) {
+            NumRepr::Decimal(m, None) => Block::text(m),
+            NumRepr::Decimal(m, Some(e)) => {
+                Block::text(m + "·10").raise(Block::text(e.to_string()))
+            }
+            NumRepr::Fraction(p, q) if p < 0 => Block::text("-").beside(Block::fraction(
+                Block::text((-p).to_string()),
+                Block::text(q.to_string()),
+            )),
+            NumRepr::Fraction(p, q) => {
+                Block::fraction(Block::text(p.to_string()), Block::text(q.to_string()))
+            }
+            NumRepr::Ln(k) => Block::text(format!("ln {k}")),
+        },
+    }
+}
+
+fn render_paren(expr: &Expr, paren: bool, format: &NumFormat) -> Block {
+    let block = render(expr, format);
+    if paren {
+        block.paren()
+    } else {
+        block
+    }
+}
+
+/// Render the argument of a function, a unit is separated by a space and anything else is
+/// wrapped in parentheses.
+fn render_arg(expr: &Expr, format: &NumFormat) -> Block {
+    if expr.is_unit() && !is_neg(expr) {
+        Block::text(" ").beside(render(expr, format))
+    } else {
+        render(expr, format).paren()
+    }
+}
+
+/// Whether the expr is written as a single word that can take a superscript.
+fn is_plain(expr: &Expr, format: &NumFormat) -> bool {
+    match expr {
+        Var | Num(E) => true,
+        Num(n) => !is_neg(expr) && matches!(format.repr(*n), NumRepr::Decimal(_, None)),
+        _ => false,
+    }
+}
+
+fn is_neg(expr: &Expr) -> bool {
+    matches!(expr, Num(n) if n.is_sign_negative())
+}
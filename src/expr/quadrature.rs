@@ -0,0 +1,151 @@
+use crate::prelude::*;
+
+/// Halvings of an interval by adaptive Simpson before giving up on the tolerance.
+const MAX_DEPTH: usize = 50;
+/// Rows of the Romberg table before giving up on the tolerance.
+const MAX_ROMBERG: usize = 20;
+
+/// A definite integral computed numerically, the exact value is within about `error` of
+/// `value`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Integral {
+    pub value: f64,
+    pub error: f64,
+}
+
+impl Expr {
+    /// The integral from `a` to `b` with adaptive Simpson, for when [`Expr::integrate`] finds
+    /// no antiderivative. `None` if the expr is not finite somewhere it is evaluated, like at
+    /// a pole.
+    pub fn integrate_numeric(&self, a: f64, b: f64, tol: f64) -> Option<Integral> {
+        self.simpson(a, b, tol)
+    }
+
+    /// Adaptive Simpson's rule, the parts of the interval where the estimates with one and two
+    /// parabolas differ by more than their share of `tol` are split again.
+    pub fn simpson(&self, a: f64, b: f64, tol: f64) -> Option<Integral> {
+        let f = self.clone().expand_derivatives();
+        let eval = |x: f64| Some(f.evaluate(x)).filter(|y| y.is_finite());
+
+        let m = a + (b - a) / 2.0;
+        let (fa, fm, fb) = (eval(a)?, eval(m)?, eval(b)?);
+        let whole = (b - a) / 6.0 * (fa + 4.0 * fm + fb);
+        simpson(&eval, [a, m, b], [fa, fm, fb], whole, tol, MAX_DEPTH)
+    }
+
+    /// Gauss–Legendre quadrature with `n` nodes, exact for polynomials of degree `2n - 1`. The
+    /// error is estimated with `2n` nodes, which also give the value.
+    pub fn gauss_legendre(&self, a: f64, b: f64, n: usize) -> Option<Integral> {
+        let f = self.clone().expand_derivatives();
+        let rule = |n: usize| {
+            let (half, mid) = ((b - a) / 2.0, a + (b - a) / 2.0);
+            let sum: f64 = legendre_nodes(n)
+                .into_iter()
+                .map(|(x, w)| w * f.evaluate(mid + half * x))
+                .sum();
+            Some(half * sum).filter(|v| v.is_finite())
+        };
+
+        let (coarse, fine) = (rule(n.max(1))?, rule(2 * n.max(1))?);
+        Some(Integral {
+            value: fine,
+            error: (fine - coarse).abs(),
+        })
+    }
+
+    /// Romberg integration, the trapezoidal rule with `2^k` parts extrapolated to no width,
+    /// until two diagonal entries differ by at most `tol`.
+    pub fn romberg(&self, a: f64, b: f64, tol: f64) -> Option<Integral> {
+        let f = self.clone().expand_derivatives();
+        let eval = |x: f64| Some(f.evaluate(x)).filter(|y| y.is_finite());
+
+        let mut h = b - a;
+        let mut prev = vec![h / 2.0 * (eval(a)? + eval(b)?)];
+        for k in 1..MAX_ROMBERG {
+            h /= 2.0;
+            let mut sum = 0.0;
+            for i in 0..1_usize << (k - 1) {
+                sum += eval(a + (2 * i + 1) as f64 * h)?;
+            }
+
+            let mut row = vec![prev[0] / 2.0 + h * sum];
+            let mut factor = 1.0;
+            for j in 1..=k {
+                factor *= 4.0;
+                row.push(row[j - 1] + (row[j - 1] - prev[j - 1]) / (factor - 1.0));
+            }
+
+            let error = (row[k] - prev[k - 1]).abs();
+            if error <= tol {
+                return Some(Integral {
+                    value: row[k],
+                    error,
+                });
+            }
+            prev = row;
+        }
+
+        let value = prev[prev.len() - 1];
+        Some(Integral {
+            value,
+            error: (value - prev[prev.len() - 2]).abs(),
+        })
+    }
+}
+
+/// Simpson on `[a, m, b]`, with `whole` the estimate with one parabola. The estimate with two
+/// parabolas is more precise by about 15 times their difference.
+fn simpson(
+    eval: &impl Fn(f64) -> Option<f64>,
+    [a, m, b]: [f64; 3],
+    [fa, fm, fb]: [f64; 3],
+    whole: f64,
+    tol: f64,
+    depth: usize,
+) -> Option<Integral> {
+    let (lm, rm) = (a + (m - a) / 2.0, m + (b - m) / 2.0);
+    let (flm, frm) = (eval(lm)?, eval(rm)?);
+    let left = (m - a) / 6.0 * (fa + 4.0 * flm + fm);
+    let right = (b - m) / 6.0 * (fm + 4.0 * frm + fb);
+    let delta = left + right - whole;
+
+    if depth == 0 || delta.abs() <= 15.0 * tol {
+        return Some(Integral {
+            value: left + right + delta / 15.0,
+            error: delta.abs() / 15.0,
+        });
+    }
+
+    let l = simpson(eval, [a, lm, m], [fa, flm, fm], left, tol / 2.0, depth - 1)?;
+    let r = simpson(eval, [m, rm, b], [fm, frm, fb], right, tol / 2.0, depth - 1)?;
+    Some(Integral {
+        value: l.value + r.value,
+        error: l.error + r.error,
+    })
+}
+
+/// The nodes and weights of Gauss–Legendre on `[-1, 1]`, the roots of the Legendre
+/// polynomial of degree `n` found with Newton from the usual cosine approximation.
+fn legendre_nodes(n: usize) -> Vec<(f64, f64)> {
+    (0..n)
+        .map(|i| {
+            let mut x = (std::f64::consts::PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+            let mut derivative = 0.0;
+            for _ in 0..100 {
+                // P_n(x) and P_n'(x) with the three term recurrence
+                let (mut p, mut prev) = (1.0, 0.0);
+                for k in 1..=n {
+                    let k = k as f64;
+                    (p, prev) = (((2.0 * k - 1.0) * x * p - (k - 1.0) * prev) / k, p);
+                }
+                derivative = n as f64 * (x * p - prev) / (x * x - 1.0);
+                let step = p / derivative;
+                x -= step;
+                if step.abs() <= f64::EPSILON {
+                    break;
+                }
+            }
+            (x, 2.0 / ((1.0 - x * x) * derivative * derivative))
+        })
+        .collect()
+}
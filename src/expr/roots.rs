@@ -0,0 +1,392 @@
+use std::ops::{Add, Div, Mul, RangeInclusive, Sub};
+
+use crate::{prelude::*, Polynomial};
+
+/// Iterations of the numerical methods before giving up.
+const MAX_ITERATIONS: usize = 500;
+/// The absolute tolerance of Brent's method, added to the relative one so a root at 0 ends.
+const BRENT_TOLERANCE: f64 = 1e-15;
+/// Subintervals an interval is split in to look for sign changes.
+const SAMPLES: usize = 1000;
+/// A root of a polynomial with an imaginary part smaller than this is real, a double root
+/// is only found to about the square root of the precision.
+const REAL_TOLERANCE: f64 = 1e-6;
+
+/// A complex number, for the roots of a polynomial.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    pub fn norm(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    pub fn is_real(self) -> bool {
+        self.im.abs() <= REAL_TOLERANCE * (1.0 + self.re.abs())
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        let d = rhs.re * rhs.re + rhs.im * rhs.im;
+        Self::new(
+            (self.re * rhs.re + self.im * rhs.im) / d,
+            (self.im * rhs.re - self.re * rhs.im) / d,
+        )
+    }
+}
+
+/// A root found numerically, the exact root is within `error` of `x`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Root {
+    pub x: f64,
+    pub error: f64,
+}
+
+impl Polynomial {
+    /// Every complex root, with multiplicity. Degree 1 and 2 use the closed forms, higher
+    /// degrees use the Durand–Kerner method. The zero polynomial gives no root.
+    pub fn complex_roots(&self) -> Vec<Complex> {
+        let c = self.coefs();
+        match c.len() {
+            0 | 1 => vec![],
+            2 => vec![Complex::new(-c[0] / c[1], 0.0)],
+            3 => quadratic(c[2], c[1], c[0]),
+            _ => self.durand_kerner(),
+        }
+    }
+
+    /// Every real root in increasing order, with multiplicity.
+    pub fn real_roots(&self) -> Vec<Root> {
+        let derivative = self.derive();
+        let mut roots: Vec<_> = self
+            .complex_roots()
+            .into_iter()
+            .filter(|z| z.is_real())
+            .map(|z| polish(self, &derivative, z.re))
+            .collect();
+        roots.sort_by(|a, b| a.x.total_cmp(&b.x));
+        roots
+    }
+
+    fn durand_kerner(&self) -> Vec<Complex> {
+        let c = self.coefs();
+        let lead = c[c.len() - 1];
+        let monic: Vec<_> = c.iter().map(|&a| Complex::new(a / lead, 0.0)).collect();
+        let eval = |z: Complex| {
+            monic
+                .iter()
+                .rev()
+                .fold(Complex::new(0.0, 0.0), |acc, &a| acc * z + a)
+        };
+
+        // the usual start, powers of a number that is neither real nor a root of unity
+        let seed = Complex::new(0.4, 0.9);
+        let mut roots: Vec<_> = (0..c.len() - 1)
+            .scan(Complex::new(1.0, 0.0), |z, _| {
+                *z = *z * seed;
+                Some(*z)
+            })
+            .collect();
+
+        for _ in 0..MAX_ITERATIONS {
+            let mut change: f64 = 0.0;
+            for i in 0..roots.len() {
+                let denominator = roots
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .fold(Complex::new(1.0, 0.0), |acc, (_, &r)| acc * (roots[i] - r));
+                let step = eval(roots[i]) / denominator;
+                roots[i] = roots[i] - step;
+                change = change.max(step.norm());
+            }
+            if change <= f64::EPSILON {
+                break;
+            }
+        }
+
+        roots
+    }
+}
+
+/// The roots of `a x^2 + b x + c`, computed without cancellation.
+fn quadratic(a: f64, b: f64, c: f64) -> Vec<Complex> {
+    let d = b * b - 4.0 * a * c;
+    if d >= 0.0 {
+        let q = -(b + d.sqrt().copysign(b)) / 2.0;
+        let mut roots = vec![q / a, if q == 0.0 { 0.0 } else { c / q }];
+        roots.sort_by(f64::total_cmp);
+        roots.into_iter().map(|r| Complex::new(r, 0.0)).collect()
+    } else {
+        let (re, im) = (-b / (2.0 * a), (-d).sqrt() / (2.0 * a.abs()));
+        vec![Complex::new(re, -im), Complex::new(re, im)]
+    }
+}
+
+/// A few Newton steps to get back the precision lost by a root found in the complex plane.
+///
+/// A polynomial of degree n always has a root within n times the Newton step of any point,
+/// which is the error once the rounding error of the value is added to it.
+fn polish(p: &Polynomial, derivative: &Polynomial, mut x: f64) -> Root {
+    for _ in 0..8 {
+        let d = derivative.evaluate(x);
+        if d == 0.0 {
+            break;
+        }
+        let next = x - p.evaluate(x) / d;
+        if !next.is_finite() || p.evaluate(next).abs() >= p.evaluate(x).abs() {
+            break;
+        }
+        x = next;
+    }
+
+    let n = p.degree().unwrap_or(0) as f64;
+    let magnitude = p
+        .coefs()
+        .iter()
+        .rev()
+        .fold(0.0, |acc, c| acc * x.abs() + c.abs());
+    let value = p.evaluate(x).abs() + 2.0 * n * f64::EPSILON * magnitude;
+    Root {
+        x,
+        error: n * value / derivative.evaluate(x).abs(),
+    }
+}
+
+impl Expr {
+    /// Newton's method from `x0` with the symbolic derivative, `None` if it does not converge.
+    pub fn newton(&self, x0: f64) -> Option<f64> {
+        let derivative = self.clone().expand_derivatives().derive();
+
+        let mut x = x0;
+        for _ in 0..MAX_ITERATIONS {
+            let step = self.evaluate(x) / derivative.evaluate(x);
+            if !step.is_finite() {
+                return None;
+            }
+            x -= step;
+            if step.abs() <= 4.0 * f64::EPSILON * (1.0 + x.abs()) {
+                return Some(x);
+            }
+        }
+        None
+    }
+
+    /// Bisection between two points where the expr has opposite signs, `None` if it does not
+    /// or if a point is not finite.
+    pub fn bisection(&self, a: f64, b: f64) -> Option<Root> {
+        if !a.is_finite() || !b.is_finite() {
+            return None;
+        }
+
+        let (mut a, mut b) = (a, b);
+        let (mut fa, fb) = (self.evaluate(a), self.evaluate(b));
+        if fa == 0.0 {
+            return Some(Root { x: a, error: 0.0 });
+        }
+        if fb == 0.0 {
+            return Some(Root { x: b, error: 0.0 });
+        }
+        if !opposite_signs(fa, fb) {
+            return None;
+        }
+
+        loop {
+            // `b - a` can overflow
+            let m = a / 2.0 + b / 2.0;
+            if m == a || m == b {
+                return Some(Root {
+                    x: m,
+                    error: (b - a).abs(),
+                });
+            }
+            let fm = self.evaluate(m);
+            if fm == 0.0 {
+                return Some(Root { x: m, error: 0.0 });
+            }
+            if (fm < 0.0) == (fa < 0.0) {
+                (a, fa) = (m, fm);
+            } else {
+                b = m;
+            }
+        }
+    }
+
+    /// Brent's method between two points where the expr has opposite signs, `None` if it
+    /// does not or if the sign changes at a pole.
+    pub fn brent(&self, a: f64, b: f64) -> Option<Root> {
+        brent(|x| self.evaluate(x), a, b)
+    }
+
+    /// Every root in the interval, found with Brent's method where the sign changes. A root
+    /// where the sign does not change, like the one of `x^2`, is found where the derivative
+    /// changes sign if the expr is almost 0 there.
+    pub fn roots(&self, interval: RangeInclusive<f64>) -> Vec<Root> {
+        let f = self.clone().expand_derivatives();
+        let mut roots = sign_changes(|x| f.evaluate(x), &interval);
+
+        let scale = samples(&interval)
+            .map(|x| f.evaluate(x).abs())
+            .filter(|y| y.is_finite())
+            .fold(1.0, f64::max);
+        let derivative = f.clone().derive();
+        for c in sign_changes(|x| derivative.evaluate(x), &interval) {
+            let known = roots.iter().any(|r| (r.x - c.x).abs() <= r.error + c.error);
+            if !known && f.evaluate(c.x).abs() <= 1e-12 * scale {
+                roots.push(c);
+            }
+        }
+
+        roots.sort_by(|a, b| a.x.total_cmp(&b.x));
+        roots
+    }
+}
+
+fn samples(interval: &RangeInclusive<f64>) -> impl Iterator<Item = f64> {
+    let (start, end) = (*interval.start(), *interval.end());
+    (0..=SAMPLES).map(move |i| start + (end - start) * i as f64 / SAMPLES as f64)
+}
+
+/// The roots of `f` between every two samples where its sign changes.
+fn sign_changes(f: impl Fn(f64) -> f64, interval: &RangeInclusive<f64>) -> Vec<Root> {
+    let points: Vec<_> = samples(interval).map(|x| (x, f(x))).collect();
+
+    let mut roots: Vec<Root> = vec![];
+    for w in points.windows(2) {
+        let ((a, fa), (b, fb)) = (w[0], w[1]);
+        let root = if fa == 0.0 {
+            Some(Root { x: a, error: 0.0 })
+        } else if fb == 0.0 && b == *interval.end() {
+            Some(Root { x: b, error: 0.0 })
+        } else if opposite_signs(fa, fb) {
+            brent(&f, a, b)
+        } else {
+            None
+        };
+        roots.extend(root);
+    }
+    roots
+}
+
+/// Whether a root is between two values, false if one is NaN.
+fn opposite_signs(a: f64, b: f64) -> bool {
+    a * b < 0.0
+}
+
+fn brent(f: impl Fn(f64) -> f64, a: f64, b: f64) -> Option<Root> {
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return Some(Root { x: a, error: 0.0 });
+    }
+    if fb == 0.0 {
+        return Some(Root { x: b, error: 0.0 });
+    }
+    if !opposite_signs(fa, fb) {
+        return None;
+    }
+
+    // a pole changes the sign too, but the values grow as the bracket shrinks around it
+    let bound = [fa, fb]
+        .into_iter()
+        .filter(|v| v.is_finite())
+        .fold(0.0, |max, v| v.abs().max(max));
+
+    // the root is always between b and c, b is the best guess
+    let (mut c, mut fc) = (b, fb);
+    let (mut d, mut e) = (b - a, b - a);
+    for _ in 0..MAX_ITERATIONS {
+        if (fb > 0.0) == (fc > 0.0) {
+            (c, fc) = (a, fa);
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            (a, fa) = (b, fb);
+            (b, fb) = (c, fc);
+            (c, fc) = (a, fa);
+        }
+
+        let tol = 2.0 * f64::EPSILON * b.abs() + BRENT_TOLERANCE / 2.0;
+        let m = (c - b) / 2.0;
+        if m.abs() <= tol || fb == 0.0 {
+            break;
+        }
+
+        if e.abs() >= tol && fa.abs() > fb.abs() {
+            // inverse quadratic interpolation, or secant when only two points are known
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * m * s, 1.0 - s)
+            } else {
+                let (q, r) = (fa / fc, fb / fc);
+                (
+                    s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+
+            if 2.0 * p < (3.0 * m * q - (tol * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = d;
+            }
+        } else {
+            d = m;
+            e = d;
+        }
+
+        (a, fa) = (b, fb);
+        b += if d.abs() > tol { d } else { tol.copysign(m) };
+        fb = f(b);
+    }
+
+    (fb.abs() <= bound).then(|| Root {
+        x: b,
+        error: if fb == 0.0 { 0.0 } else { (c - b).abs() },
+    })
+}
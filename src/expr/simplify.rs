@@ -0,0 +1,61 @@
+use crate::{prelude::*, rewrite::fixed_point};
+
+impl Expr {
+    /// Fold constants and remove identities like `x * 1` or `x + 0`. This is not a CAS, it only
+    /// cleans up what `derive` produces.
+    pub fn simplify(self) -> Self {
+        fixed_point(self, Expr::simplify_once)
+    }
+
+    fn simplify_once(self) -> Self {
+        match self {
+            Bin(t, a, b) => simplify_bin(t, a.simplify_once(), b.simplify_once()),
+            Trig(t, a) => match a.simplify_once() {
+                a @ Num(_) => Num(a.trig(t).evaluate(0.0)),
+                a => a.trig(t),
+            },
+            Func(func, a) => match a.simplify_once() {
+                Num(n) => Num(func.eval(n)),
+                a => a.func(&func),
+            },
+            Unknown(name, n, a) => a.simplify_once().unknown(name, n),
+            Derivative(0, a) => a.simplify_once(),
+            Derivative(n, a) => a.simplify_once().derivative(n),
+            e => e,
+        }
+    }
+}
+
+fn simplify_bin(t: crate::Bin, a: Expr, b: Expr) -> Expr {
+    match (t, a, b) {
+        (t, a @ Num(_), b @ Num(_)) => Num(Bin(t, Box::new(a), Box::new(b)).evaluate(0.0)),
+
+        (Add, Num(0.0), e) | (Add, e, Num(0.0)) | (Sub, e, Num(0.0)) => e,
+        (Add, a, Num(n)) if n < 0.0 => a.sub(-n),
+        (Sub, a, Num(n)) if n < 0.0 => a.add(-n),
+        (Sub, a, b) if a == b => Num(0.0),
+
+        (Mul, Num(0.0), _) | (Mul, _, Num(0.0)) => Num(0.0),
+        (Mul, Num(1.0), e) | (Mul, e, Num(1.0)) => e,
+        // keep the constant on the left and merge it with nested ones
+        (Mul, e, n @ Num(_)) => simplify_bin(Mul, n, e),
+        (Mul, Num(n), Bin(Mul, a, b)) if a.is_num() => {
+            simplify_bin(Mul, simplify_bin(Mul, Num(n), *a), *b)
+        }
+        (Mul, Bin(Mul, a, b), c) if a.is_num() => simplify_bin(Mul, *a, simplify_bin(Mul, *b, c)),
+        (Mul, a, Bin(Mul, b, c)) if b.is_num() => simplify_bin(Mul, *b, simplify_bin(Mul, a, *c)),
+
+        (Div, Num(0.0), _) => Num(0.0),
+        (Div, e, Num(1.0)) => e,
+        (Div, a, b) if a == b => Num(1.0),
+
+        (Exp, _, Num(0.0)) => Num(1.0),
+        (Exp, e, Num(1.0)) => e,
+        (Exp, Num(1.0), _) => Num(1.0),
+
+        (Log, _, Num(1.0)) => Num(0.0),
+        (Log, a, b) if a == b => Num(1.0),
+
+        (t, a, b) => Bin(t, Box::new(a), Box::new(b)),
+    }
+}
@@ -0,0 +1,31 @@
+use crate::prelude::*;
+
+impl Expr {
+    /// Replace every part of this expr equal to `var` by `replacement`, usually `var` is
+    /// [`Var`]. A [`Derivative`] is derived first, as the derivative of the replaced expr is
+    /// not the same.
+    pub fn substitute(self, var: &Expr, replacement: &Expr) -> Self {
+        if self == *var {
+            return replacement.clone();
+        }
+
+        match self {
+            Bin(t, a, b) => Bin(
+                t,
+                Box::new(a.substitute(var, replacement)),
+                Box::new(b.substitute(var, replacement)),
+            ),
+            Trig(t, a) => a.substitute(var, replacement).trig(t),
+            Func(func, a) => a.substitute(var, replacement).func(&func),
+            Unknown(name, n, a) => a.substitute(var, replacement).unknown(name, n),
+            Derivative(n, f) => f.derive_n(n).substitute(var, replacement),
+            e => e,
+        }
+    }
+
+    /// Compose this expr with another, if this expr is `f(x)` and the other is `g(x)`, the
+    /// result will be `f(g(x))`.
+    pub fn compose(self, inner: impl Into<Expr>) -> Self {
+        self.substitute(&Var, &inner.into())
+    }
+}
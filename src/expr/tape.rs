@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::{prelude::*, Bin, Function, Trig};
+
+/// An operation of a [`Tape`], its args are earlier operations.
+#[derive(Clone, Debug)]
+enum Op {
+    Num(f64),
+    /// The variable with this index.
+    Input(usize),
+    Bin(Bin, usize, usize),
+    Trig(Trig, usize),
+    Func(Arc<Function>, usize),
+}
+
+/// An expr flattened into a list of operations, every one after its args, to get the value
+/// and the partial derivatives of every variable with one pass forward and one backward.
+/// The variables are `x`, named `"x"`, and every [`Param`].
+#[derive(Clone, Debug)]
+pub struct Tape {
+    ops: Vec<Op>,
+    /// Whether each operation depends on a variable, the others get no derivative.
+    varying: Vec<bool>,
+    variables: Vec<String>,
+}
+
+impl Tape {
+    /// Record the operations of the expr, an unknown function is NaN.
+    pub fn new(expr: &Expr) -> Self {
+        let mut tape = Tape {
+            ops: vec![],
+            varying: vec![],
+            variables: vec![],
+        };
+        tape.record(&expr.clone().expand_derivatives());
+        tape
+    }
+
+    /// The variables in the order they first appear in the expr.
+    pub fn variables(&self) -> &[String] {
+        &self.variables
+    }
+
+    fn record(&mut self, expr: &Expr) -> usize {
+        let (op, varying) = match expr {
+            Bin(t, a, b) => {
+                let (a, b) = (self.record(a), self.record(b));
+                (Op::Bin(*t, a, b), self.varying[a] || self.varying[b])
+            }
+            Trig(t, a) => {
+                let a = self.record(a);
+                (Op::Trig(*t, a), self.varying[a])
+            }
+            Func(func, a) => {
+                let a = self.record(a);
+                (Op::Func(func.clone(), a), self.varying[a])
+            }
+            Var => (Op::Input(self.variable("x")), true),
+            Param(name) => (Op::Input(self.variable(name)), true),
+            Num(n) => (Op::Num(*n), false),
+            Unknown(..) => (Op::Num(f64::NAN), false),
+            Derivative(..) => unreachable!("derivatives are expanded"),
+        };
+        self.ops.push(op);
+        self.varying.push(varying);
+        self.ops.len() - 1
+    }
+
+    fn variable(&mut self, name: &str) -> usize {
+        match self.variables.iter().position(|v| v == name) {
+            Some(i) => i,
+            None => {
+                self.variables.push(name.into());
+                self.variables.len() - 1
+            }
+        }
+    }
+
+    /// The value of the expr with the variables at `values`, in the order of
+    /// [`Tape::variables`].
+    ///
+    /// # Panics
+    ///
+    /// If there is not one value per variable.
+    pub fn evaluate(&self, values: &[f64]) -> f64 {
+        self.forward(values)[self.ops.len() - 1]
+    }
+
+    /// The value of the expr and its partial derivative for every variable, in the order of
+    /// [`Tape::variables`].
+    ///
+    /// # Panics
+    ///
+    /// If there is not one value per variable.
+    pub fn gradient(&self, values: &[f64]) -> (f64, Vec<f64>) {
+        let v = self.forward(values);
+        let mut adjoint = vec![0.0; self.ops.len()];
+        let mut gradient = vec![0.0; self.variables.len()];
+        adjoint[self.ops.len() - 1] = 1.0;
+
+        for (i, op) in self.ops.iter().enumerate().rev() {
+            let g = adjoint[i];
+            if !self.varying[i] || g == 0.0 {
+                continue;
+            }
+
+            // the partial derivative of the operation for each of its args
+            let partials = match op {
+                Op::Num(_) => [None, None],
+                Op::Input(k) => {
+                    gradient[*k] += g;
+                    [None, None]
+                }
+                Op::Bin(t, a, b) => {
+                    let (x, y) = (v[*a], v[*b]);
+                    let (da, db) = match t {
+                        Add => (1.0, 1.0),
+                        Sub => (1.0, -1.0),
+                        Mul => (y, x),
+                        Div => (1.0 / y, -v[i] / y),
+                        Exp => (y * x.powf(y - 1.0), v[i] * x.ln()),
+                        // log_x y = ln y / ln x
+                        Log => (-v[i] / (x * x.ln()), 1.0 / (y * x.ln())),
+                    };
+                    [Some((*a, da)), Some((*b, db))]
+                }
+                Op::Trig(t, a) => {
+                    let (sin, cos) = v[*a].sin_cos();
+                    let d = match t {
+                        Sin => cos,
+                        Cos => -sin,
+                        Tan => 1.0 / (cos * cos),
+                        Csc => -cos / (sin * sin),
+                        Sec => sin / (cos * cos),
+                        Cot => -1.0 / (sin * sin),
+                    };
+                    [Some((*a, d)), None]
+                }
+                Op::Func(func, a) => {
+                    let d = func.derivative().evaluate(v[*a]);
+                    [Some((*a, d)), None]
+                }
+            };
+
+            for (arg, d) in partials.into_iter().flatten() {
+                // a constant arg, like the base of `2^x`, has no derivative to add
+                if self.varying[arg] {
+                    adjoint[arg] += g * d;
+                }
+            }
+        }
+
+        (v[self.ops.len() - 1], gradient)
+    }
+
+    fn forward(&self, values: &[f64]) -> Vec<f64> {
+        assert_eq!(
+            values.len(),
+            self.variables.len(),
+            "one value is needed per variable"
+        );
+
+        let mut v: Vec<f64> = Vec::with_capacity(self.ops.len());
+        for op in &self.ops {
+            let value = match op {
+                Op::Num(n) => *n,
+                Op::Input(k) => values[*k],
+                Op::Bin(t, a, b) => {
+                    let (x, y) = (v[*a], v[*b]);
+                    match t {
+                        Add => x + y,
+                        Sub => x - y,
+                        Mul => x * y,
+                        Div => x / y,
+                        Exp => x.powf(y),
+                        Log => y.log(x),
+                    }
+                }
+                Op::Trig(t, a) => {
+                    let (sin, cos) = v[*a].sin_cos();
+                    match t {
+                        Sin => sin,
+                        Cos => cos,
+                        Tan => sin / cos,
+                        Csc => sin.recip(),
+                        Sec => cos.recip(),
+                        Cot => cos / sin,
+                    }
+                }
+                Op::Func(func, a) => func.eval(v[*a]),
+            };
+            v.push(value);
+        }
+        v
+    }
+}
+
+impl Expr {
+    /// The partial derivative of the expr for `x`, named `"x"`, and for every [`Param`] at
+    /// the point, with one pass on a [`Tape`]. A variable missing from the point is NaN.
+    pub fn gradient(&self, point: &HashMap<String, f64>) -> HashMap<String, f64> {
+        let tape = Tape::new(self);
+        let values: Vec<_> = tape
+            .variables()
+            .iter()
+            .map(|name| point.get(name).copied().unwrap_or(f64::NAN))
+            .collect();
+        let (_, gradient) = tape.gradient(&values);
+        tape.variables.iter().cloned().zip(gradient).collect()
+    }
+}
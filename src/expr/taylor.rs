@@ -0,0 +1,83 @@
+use crate::prelude::*;
+
+/// Points where the next derivative is evaluated to bound the remainder.
+const REMAINDER_SAMPLES: usize = 200;
+
+impl Expr {
+    /// The Taylor polynomial of the given order around `center`, lowest power first, like
+    /// `1 + 2 * (x - 1) + (x - 1)^2`. The coefficients are the successive derivatives at the
+    /// center divided by `k!`, so they are exact when the derivatives are.
+    pub fn taylor(&self, center: f64, order: usize) -> Expr {
+        let shifted = if center == 0.0 {
+            Var
+        } else if center < 0.0 {
+            Var.add(-center)
+        } else {
+            Var.sub(center)
+        };
+
+        let mut series: Option<Expr> = None;
+        for (k, coef) in self.taylor_coefs(center).take(order + 1).enumerate() {
+            if coef == 0.0 {
+                continue;
+            }
+
+            let power = match k {
+                0 => None,
+                1 => Some(shifted.clone()),
+                k => Some(shifted.clone().exp(k)),
+            };
+            let term = |coef: f64| match &power {
+                None => Num(coef),
+                Some(p) if coef == 1.0 => p.clone(),
+                Some(p) => Num(coef).mul(p.clone()),
+            };
+
+            series = Some(match series {
+                None => term(coef),
+                Some(s) if coef < 0.0 => s.sub(term(-coef)),
+                Some(s) => s.add(term(coef)),
+            });
+        }
+
+        series.unwrap_or(Num(0.0))
+    }
+
+    /// The coefficients of the Taylor series, the constant first. Each one takes one more
+    /// derivative, so only take the ones needed.
+    pub(crate) fn taylor_coefs(&self, center: f64) -> impl Iterator<Item = f64> {
+        let mut derivative = self.clone().expand_derivatives().simplify();
+        let mut factorial = 1.0;
+        (0..).map(move |k| {
+            if k > 0 {
+                derivative = derivative.clone().derive().simplify();
+                factorial *= k as f64;
+            }
+            derivative.evaluate(center) / factorial
+        })
+    }
+
+    /// The Taylor polynomial around 0.
+    pub fn maclaurin(&self, order: usize) -> Expr {
+        self.taylor(0.0, order)
+    }
+
+    /// A bound of the error of [`Expr::taylor`] at `x` with the Lagrange remainder,
+    /// `max |f^(n+1)| * |x - center|^(n+1) / (n+1)!`. The max is estimated by sampling the
+    /// derivative between the center and `x`, so a narrow peak can be missed.
+    pub fn taylor_remainder(&self, center: f64, order: usize, x: f64) -> f64 {
+        let next = self
+            .clone()
+            .expand_derivatives()
+            .derive_n(order + 1)
+            .simplify();
+
+        let max = (0..=REMAINDER_SAMPLES)
+            .map(|i| center + (x - center) * i as f64 / REMAINDER_SAMPLES as f64)
+            .map(|t| next.evaluate(t).abs())
+            .fold(0.0, f64::max);
+        let factorial: f64 = (1..=order + 1).map(|k| k as f64).product();
+
+        max * (x - center).abs().powi(order as i32 + 1) / factorial
+    }
+}
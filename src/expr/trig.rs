@@ -0,0 +1,59 @@
+use std::sync::LazyLock;
+
+use crate::{prelude::*, rewrite::Rewriter};
+
+/// Write every trig function with `sin` and `cos`.
+const SIN_COS: &str = "
+tan ?a -> sin ?a / cos ?a
+cot ?a -> cos ?a / sin ?a
+sec ?a -> 1 / cos ?a
+csc ?a -> 1 / sin ?a
+";
+
+/// Identities on an expr written with `sin` and `cos`.
+const IDENTITIES: &str = "
+# pythagorean
+(sin ?a)^2 + (cos ?a)^2 -> 1
+(cos ?a)^2 + (sin ?a)^2 -> 1
+1 - (sin ?a)^2 -> (cos ?a)^2
+1 - (cos ?a)^2 -> (sin ?a)^2
+
+# double angle
+2 * sin ?a * cos ?a -> sin (2 * ?a)
+2 * cos ?a * sin ?a -> sin (2 * ?a)
+2 * (sin ?a * cos ?a) -> sin (2 * ?a)
+2 * (cos ?a * sin ?a) -> sin (2 * ?a)
+(cos ?a)^2 - (sin ?a)^2 -> cos (2 * ?a)
+
+# reciprocals and quotients
+?a * (1 / ?b) -> ?a / ?b
+(1 / ?a) * ?b -> ?b / ?a
+(1 / ?a)^?n -> 1 / ?a^?n if ?n is num
+?a / ?b * (?c / ?d) -> ?a * ?c / (?b * ?d)
+?a / ?b / ?c -> ?a / (?b * ?c)
+?a * ?b / ?a -> ?b
+?a * ?b / ?b -> ?a
+?a * ?b / (?b * ?a) -> 1
+
+# powers
+?a * ?a -> ?a^2
+?a^?n * ?a -> ?a^(?n + 1) if ?n is num
+?a * ?a^?n -> ?a^(?n + 1) if ?n is num
+";
+
+static SIN_COS_RULES: LazyLock<Rewriter> = LazyLock::new(|| Rewriter::parse(SIN_COS).unwrap());
+static IDENTITY_RULES: LazyLock<Rewriter> = LazyLock::new(|| Rewriter::parse(IDENTITIES).unwrap());
+
+impl Expr {
+    /// Write `tan`, `cot`, `sec` and `csc` with `sin` and `cos`.
+    pub fn to_sin_cos(self) -> Self {
+        SIN_COS_RULES.rewrite(self)
+    }
+
+    /// Write the expr with `sin` and `cos`, then apply the pythagorean and double angle
+    /// identities and [`Expr::simplify`] until the expr does not change. Quotients are
+    /// cancelled as if the denominator is never 0.
+    pub fn simplify_trig(self) -> Self {
+        IDENTITY_RULES.rewrite_simplify(self.to_sin_cos())
+    }
+}
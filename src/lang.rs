@@ -1,12 +1,15 @@
+use std::fmt::Display;
 use std::str::FromStr;
 
 use crate::Expr;
 
 mod lex;
 mod parse;
+mod script;
 
 pub(crate) use lex::*;
 pub(crate) use parse::*;
+pub use script::*;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum LangError {
@@ -19,8 +22,64 @@ pub enum LangError {
     // parser error
     UncloseParen(usize),
     UnexpectedToken(usize),
+    InvalidDerivative(usize),
 }
 
+impl LangError {
+    /// The byte offset in the source where the error happened, `usize::MAX` is the end of the
+    /// source.
+    pub fn loc(&self) -> Option<usize> {
+        match self {
+            LangError::MultiLine => None,
+            LangError::InvalidFloatFormat(loc)
+            | LangError::InvalidSymbol(_, loc)
+            | LangError::InvalidWord(_, loc)
+            | LangError::UncloseParen(loc)
+            | LangError::UnexpectedToken(loc)
+            | LangError::InvalidDerivative(loc) => Some(*loc),
+        }
+    }
+
+    /// Move the location of the error by `offset`, the end of the source is moved to `offset +
+    /// len`.
+    pub(crate) fn offset(self, offset: usize, len: usize) -> Self {
+        let shift = |loc: usize| {
+            if loc == usize::MAX {
+                offset + len
+            } else {
+                offset + loc
+            }
+        };
+
+        match self {
+            LangError::MultiLine => LangError::MultiLine,
+            LangError::InvalidFloatFormat(l) => LangError::InvalidFloatFormat(shift(l)),
+            LangError::InvalidSymbol(c, l) => LangError::InvalidSymbol(c, shift(l)),
+            LangError::InvalidWord(w, l) => LangError::InvalidWord(w, shift(l)),
+            LangError::UncloseParen(l) => LangError::UncloseParen(shift(l)),
+            LangError::UnexpectedToken(l) => LangError::UnexpectedToken(shift(l)),
+            LangError::InvalidDerivative(l) => LangError::InvalidDerivative(shift(l)),
+        }
+    }
+}
+
+impl Display for LangError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LangError::MultiLine => write!(f, "expected a single line"),
+            LangError::InvalidFloatFormat(_) => write!(f, "invalid number"),
+            LangError::InvalidSymbol(c, _) => write!(f, "unexpected character `{c}`"),
+            LangError::InvalidWord(w, _) => write!(f, "unknown word `{w}`"),
+            LangError::UncloseParen(_) => write!(f, "unclosed parenthesis"),
+            LangError::UnexpectedToken(usize::MAX) => write!(f, "unexpected end of input"),
+            LangError::UnexpectedToken(_) => write!(f, "unexpected token"),
+            LangError::InvalidDerivative(_) => write!(f, "invalid derivative order"),
+        }
+    }
+}
+
+impl std::error::Error for LangError {}
+
 type Res<T> = Result<T, LangError>;
 
 impl FromStr for Expr {
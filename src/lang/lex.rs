@@ -1,12 +1,20 @@
+use std::f64::consts::E;
+
 use super::{LangError, Res};
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum TokenType {
     OpenParen,
     CloseParen,
 
     Num(f64),
     Var,
+    /// Any word that is not a keyword, like a name bound in a script.
+    Ident(String),
+    /// A word after `?` in a rewrite rule, like `?a`.
+    Hole(String),
+    /// A word after `$`, a named value like `$a`.
+    Param(String),
 
     Plus,
     Minus,
@@ -14,8 +22,11 @@ pub enum TokenType {
     Slash,
     Caret,
     Underscore,
+    Prime,
+    Equal,
 
     Log,
+    Ln,
     Sin,
     Cos,
     Tan,
@@ -23,11 +34,16 @@ pub enum TokenType {
     Sec,
     Cot,
 
+    /// `d` of `d/dx`
+    D,
+    /// `dx` of `d/dx`
+    Dx,
+
     #[allow(clippy::upper_case_acronyms)]
     END,
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone)]
 pub(crate) struct Token {
     pub token: TokenType,
     pub loc: usize,
@@ -62,6 +78,23 @@ pub(crate) fn lex(source: &str) -> Res<Vec<Token>> {
             continue;
         }
 
+        if char == '?' || char == '$' {
+            let mut name = String::new();
+            while let Some((_, c)) = src.next_if(|&(_, c)| c.is_alphabetic()) {
+                name.push(c);
+            }
+            if name.is_empty() {
+                return Err(LangError::InvalidSymbol(char, loc));
+            }
+
+            let token = match char {
+                '?' => TokenType::Hole(name),
+                _ => TokenType::Param(name),
+            };
+            tokens.push(token.at(loc));
+            continue;
+        }
+
         if !char.is_alphanumeric() {
             let tk_opt = match char {
                 '+' => Some(TokenType::Plus),
@@ -70,6 +103,8 @@ pub(crate) fn lex(source: &str) -> Res<Vec<Token>> {
                 '/' => Some(TokenType::Slash),
                 '^' => Some(TokenType::Caret),
                 '_' => Some(TokenType::Underscore),
+                '\'' => Some(TokenType::Prime),
+                '=' => Some(TokenType::Equal),
                 '(' => Some(TokenType::OpenParen),
                 ')' => Some(TokenType::CloseParen),
                 _ => None,
@@ -106,6 +141,7 @@ pub(crate) fn lex(source: &str) -> Res<Vec<Token>> {
         } else {
             let word_opt = match acc.as_str() {
                 "log" => Some(TokenType::Log),
+                "ln" => Some(TokenType::Ln),
 
                 "sin" => Some(TokenType::Sin),
                 "cos" => Some(TokenType::Cos),
@@ -115,15 +151,14 @@ pub(crate) fn lex(source: &str) -> Res<Vec<Token>> {
                 "cot" => Some(TokenType::Cot),
 
                 "x" => Some(TokenType::Var),
+                "d" => Some(TokenType::D),
+                "dx" => Some(TokenType::Dx),
+                "e" => Some(TokenType::Num(E)),
 
                 _ => None,
             };
 
-            if let Some(token) = word_opt {
-                tokens.push(token.at(loc));
-            } else {
-                return Err(LangError::InvalidWord(acc, loc));
-            }
+            tokens.push(word_opt.unwrap_or(TokenType::Ident(acc)).at(loc));
         }
     }
 
@@ -131,3 +166,8 @@ pub(crate) fn lex(source: &str) -> Res<Vec<Token>> {
 
     Ok(tokens)
 }
+
+/// Whether a name is read as a word of its own, not a keyword or a number.
+pub(crate) fn is_ident(name: &str) -> bool {
+    matches!(lex(name).as_deref(), Ok([t, _]) if matches!(&t.token, TokenType::Ident(n) if n == name))
+}
@@ -1,17 +1,36 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 
 use crate::{
     lang::lex::{Token, TokenType},
-    Expr, Trig,
+    Expr, Registry, Trig,
 };
 
 use super::{LangError, Res};
 
-struct Parser {
+struct Parser<'a> {
     tokens: VecDeque<Token>,
+    /// Names that are replaced by their expr when used.
+    bindings: &'a HashMap<String, Expr>,
+    /// Functions that can be called by name.
+    functions: &'a Registry,
+    /// Whether holes like `?a` are allowed, they are parsed as an unknown function named `?a`.
+    holes: bool,
 }
 
-impl Parser {
+impl Parser<'_> {
+    /// Parse an expr that has to be the whole input.
+    fn parse_all(&mut self) -> Res<Expr> {
+        let expr = self.parse()?;
+
+        match self.next_token() {
+            Token {
+                token: TokenType::END,
+                ..
+            } => Ok(expr),
+            t => Err(LangError::UnexpectedToken(t.loc)),
+        }
+    }
+
     fn parse(&mut self) -> Res<Expr> {
         self.parse_add_bin()
     }
@@ -49,7 +68,7 @@ impl Parser {
     }
 
     fn parse_func(&mut self) -> Res<Expr> {
-        Ok(match self.curr() {
+        Ok(match self.curr().clone() {
             func @ (TokenType::Sin
             | TokenType::Cos
             | TokenType::Tan
@@ -83,6 +102,35 @@ impl Parser {
                 self.parse_juxta()?.log(base)
             }
 
+            TokenType::Ln => {
+                self.next();
+
+                self.parse_juxta()?.ln()
+            }
+
+            // d/dx f or d^n/dx^n f
+            TokenType::D => {
+                self.next();
+
+                let order = self.parse_order()?;
+                self.expect_next(TokenType::Slash)?;
+                self.expect_next(TokenType::Dx)?;
+
+                let loc = self.curr_token().loc;
+                if self.parse_order()? != order {
+                    return Err(LangError::InvalidDerivative(loc));
+                }
+
+                self.parse_juxta()?.derivative(order)
+            }
+
+            TokenType::Ident(name) if self.functions.get(&name).is_some() => {
+                self.next();
+
+                let function = self.functions.get(&name).unwrap().clone();
+                self.parse_juxta()?.func(&function)
+            }
+
             _ => self.parse_juxta()?,
         })
     }
@@ -97,6 +145,7 @@ impl Parser {
                 | TokenType::Slash
                 | TokenType::Caret
                 | TokenType::CloseParen
+                | TokenType::Equal
                 | TokenType::END
         ) {
             let right = self.parse()?;
@@ -121,7 +170,38 @@ impl Parser {
         Ok(left)
     }
 
+    /// The optional `^n` of a derivative, 1 if there is none.
+    fn parse_order(&mut self) -> Res<usize> {
+        if !self.expect(TokenType::Caret) {
+            return Ok(1);
+        }
+        self.next();
+
+        let t = self.next_token();
+        match t.token {
+            TokenType::Num(n) if n.fract() == 0.0 => Ok(n as usize),
+            _ => Err(LangError::InvalidDerivative(t.loc)),
+        }
+    }
+
+    /// A unit, followed by any number of primes to derive it.
     fn parse_unit(&mut self) -> Res<Expr> {
+        let unit = self.parse_unit_inner()?;
+
+        let mut order = 0;
+        while self.expect(TokenType::Prime) {
+            self.next();
+            order += 1;
+        }
+
+        Ok(if order == 0 {
+            unit
+        } else {
+            unit.derivative(order)
+        })
+    }
+
+    fn parse_unit_inner(&mut self) -> Res<Expr> {
         let t = self.next_token();
         Ok(match t.token {
             TokenType::OpenParen => {
@@ -135,17 +215,68 @@ impl Parser {
             }
             TokenType::Num(n) => Expr::Num(n),
             TokenType::Var => Expr::Var,
+            TokenType::Hole(name) if self.holes => Expr::Var.unknown(format!("?{name}"), 0),
+            TokenType::Param(name) => Expr::param(name).map_err(|e| e.offset(t.loc, 0))?,
+            TokenType::Ident(name) => match self.bindings.get(&name) {
+                // a bound expr called like `f'(2)` is derived then composed with the arg
+                Some(expr) if self.is_call() => {
+                    let (order, arg) = self.parse_call()?;
+                    expr.clone().derive_n(order).compose(arg)
+                }
+                Some(expr) => expr.clone(),
+                // an unknown function like `f(x)` or `f'(x)`
+                None if self.is_call() => {
+                    let (order, arg) = self.parse_call()?;
+                    arg.unknown(name, order)
+                }
+                None => return Err(LangError::InvalidWord(name, t.loc)),
+            },
+
+            // negative number literal
+            TokenType::Minus => match self.next_token() {
+                Token {
+                    token: TokenType::Num(n),
+                    ..
+                } => Expr::Num(-n),
+                t => return Err(LangError::UnexpectedToken(t.loc)),
+            },
 
             _ => return Err(LangError::UnexpectedToken(t.loc)),
         })
     }
 
-    fn curr(&self) -> TokenType {
-        self.curr_token().token
+    /// The primes and the parenthesized arg after the name of a function, like `''(x)`.
+    fn parse_call(&mut self) -> Res<(usize, Expr)> {
+        let mut order = 0;
+        while self.expect(TokenType::Prime) {
+            self.next();
+            order += 1;
+        }
+        self.next();
+
+        let arg = self.parse()?;
+        if !self.expect(TokenType::CloseParen) {
+            return Err(LangError::UncloseParen(self.next_token().loc));
+        }
+        self.next();
+
+        Ok((order, arg))
     }
 
-    fn curr_token(&self) -> Token {
-        *self.tokens.front().unwrap()
+    /// Whether the next tokens are any number of primes then an open parenthesis.
+    fn is_call(&self) -> bool {
+        self.tokens
+            .iter()
+            .find(|t| t.token != TokenType::Prime)
+            .is_some_and(|t| t.token == TokenType::OpenParen)
+    }
+
+    fn curr(&self) -> &TokenType {
+        &self.curr_token().token
+    }
+
+    fn curr_token(&self) -> &Token {
+        self.tokens.front().unwrap()
     }
 
     fn next(&mut self) -> TokenType {
@@ -157,13 +288,47 @@ impl Parser {
     }
 
     fn expect(&mut self, tk: TokenType) -> bool {
-        self.curr() == tk
+        *self.curr() == tk
+    }
+
+    /// Consume the next token, failing if it is not `tk`.
+    fn expect_next(&mut self, tk: TokenType) -> Res<()> {
+        let t = self.next_token();
+        if t.token == tk {
+            Ok(())
+        } else {
+            Err(LangError::UnexpectedToken(t.loc))
+        }
     }
 }
 
 pub(crate) fn parse(tokens: Vec<Token>) -> Res<Expr> {
+    parse_with(tokens, &HashMap::new(), &Registry::new())
+}
+
+/// Parse with names that are replaced by their expr, or composed with the arg when called like
+/// `f(x + 1)`, and functions that can be called by name.
+pub(crate) fn parse_with(
+    tokens: Vec<Token>,
+    bindings: &HashMap<String, Expr>,
+    functions: &Registry,
+) -> Res<Expr> {
+    Parser {
+        tokens: tokens.into(),
+        bindings,
+        functions,
+        holes: false,
+    }
+    .parse_all()
+}
+
+/// Parse the side of a rewrite rule, where a hole `?a` is an unknown function named `?a`.
+pub(crate) fn parse_pattern(tokens: Vec<Token>) -> Res<Expr> {
     Parser {
         tokens: tokens.into(),
+        bindings: &HashMap::new(),
+        functions: &Registry::new(),
+        holes: true,
     }
-    .parse()
+    .parse_all()
 }
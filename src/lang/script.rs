@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use super::{lex, parse_with, LangError, Res, Token, TokenType};
+use crate::{Expr, Registry};
+
+/// The result of an expression line of a script.
+#[derive(Debug, PartialEq)]
+pub struct ScriptOutput {
+    /// The line of the expression, starting at 1.
+    pub line: usize,
+    /// The expression with every derivative expanded, or its value if it does not depend on
+    /// `x`.
+    pub expr: Expr,
+}
+
+/// A script of several lines. Each line is either empty, an expression, or a binding like
+/// `let g = x^2 + 1`, after which `g` can be used in later expressions. Everything after a `#`
+/// is a comment.
+///
+/// Bindings are kept between calls to [`Script::run`].
+#[derive(Debug, Default)]
+pub struct Script {
+    bindings: HashMap<String, Expr>,
+    functions: Registry,
+}
+
+impl Script {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A script that can call the functions of a registry.
+    pub fn with_functions(functions: Registry) -> Self {
+        Self {
+            functions,
+            ..Self::default()
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Expr> {
+        self.bindings.get(name)
+    }
+
+    /// Run every line of the source, the location of an error is a byte offset in the whole
+    /// source.
+    pub fn run(&mut self, source: &str) -> Res<Vec<ScriptOutput>> {
+        let mut outputs = vec![];
+        let mut offset = 0;
+
+        for (i, line) in source.split_inclusive('\n').enumerate() {
+            let code = line.split('#').next().unwrap().trim_end();
+            let line_offset = offset;
+            offset += line.len();
+
+            if code.trim().is_empty() {
+                continue;
+            }
+
+            let at_line = |e: LangError| e.offset(line_offset, code.len());
+            if let Some(expr) = self.run_line(code).map_err(at_line)? {
+                outputs.push(ScriptOutput { line: i + 1, expr });
+            }
+        }
+
+        Ok(outputs)
+    }
+
+    fn run_line(&mut self, code: &str) -> Res<Option<Expr>> {
+        let tokens = lex(code)?;
+
+        let (name, tokens) = match tokens.as_slice() {
+            [Token {
+                token: TokenType::Ident(l),
+                ..
+            }, rest @ ..]
+                if l == "let" =>
+            {
+                match rest {
+                    [Token {
+                        token: TokenType::Ident(name),
+                        ..
+                    }, Token {
+                        token: TokenType::Equal,
+                        ..
+                    }, expr @ ..] => (Some(name.clone()), expr.to_vec()),
+                    [Token {
+                        token: TokenType::Ident(_),
+                        ..
+                    }, t, ..]
+                    | [t, ..] => return Err(LangError::UnexpectedToken(t.loc)),
+                    [] => unreachable!(),
+                }
+            }
+            _ => (None, tokens),
+        };
+
+        let mut expr = parse_with(tokens, &self.bindings, &self.functions)?.expand_derivatives();
+        if expr.is_constant() {
+            expr = Expr::Num(expr.evaluate(0.0));
+        }
+
+        Ok(match name {
+            Some(name) => {
+                self.bindings.insert(name, expr);
+                None
+            }
+            None => Some(expr),
+        })
+    }
+}
+
+/// Run a script with no bindings, see [`Script`].
+pub fn run_script(source: &str) -> Res<Vec<ScriptOutput>> {
+    Script::new().run(source)
+}
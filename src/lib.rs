@@ -2,6 +2,8 @@ pub mod prelude;
 
 mod expr;
 pub mod lang;
+pub mod repl;
+pub mod rewrite;
 pub use expr::*;
 
 #[cfg(test)]
@@ -0,0 +1,281 @@
+//! An interactive session where expressions can be bound to names and derived by name.
+//!
+//! ```text
+//! f = x^2 sin x
+//! f'
+//! f''(2)
+//! simplify f'
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::{
+    lang::{is_ident, lex, parse_with, LangError, TokenType},
+    Expr, Registry,
+};
+
+/// The name the result of the last line is bound to, it can be used like any other name.
+pub const LAST: &str = "ans";
+
+/// Words that can not be used as names as they mean something in an expression.
+const RESERVED: &[&str] = &[
+    "x", "e", "d", "dx", "log", "ln", "sin", "cos", "tan", "csc", "sec", "cot", "simplify",
+    "derive",
+];
+
+#[derive(Debug, PartialEq)]
+pub enum Reply {
+    /// The line was an expression, it is now bound to [`LAST`].
+    Expr(Expr),
+    /// An expression was bound to a name.
+    Bound(String, Expr),
+    /// A function was evaluated at a point.
+    Value(f64),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ReplError {
+    /// The expression starting at the given byte offset of the line could not be parsed.
+    Lang(LangError, usize),
+    Unbound(String),
+    InvalidName(String),
+}
+
+impl ReplError {
+    /// The byte offset in the line where the error happened.
+    pub fn loc(&self) -> Option<usize> {
+        match self {
+            ReplError::Lang(e, offset) => e.loc().map(|l| l.saturating_add(*offset)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ReplError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplError::Lang(e, _) => write!(f, "{e}"),
+            ReplError::Unbound(name) => write!(f, "`{name}` is not defined"),
+            ReplError::InvalidName(name) => write!(f, "`{name}` can not be used as a name"),
+        }
+    }
+}
+
+impl std::error::Error for ReplError {}
+
+type Res<T> = Result<T, ReplError>;
+
+#[derive(Debug, Default)]
+pub struct Session {
+    bindings: HashMap<String, Expr>,
+    functions: Registry,
+    history: Vec<String>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A session that can call the functions of a registry.
+    pub fn with_functions(functions: Registry) -> Self {
+        Self {
+            functions,
+            ..Self::default()
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Expr> {
+        self.bindings.get(name)
+    }
+
+    /// Every binding, sorted by name.
+    pub fn bindings(&self) -> Vec<(&str, &Expr)> {
+        let mut bindings: Vec<_> = self.bindings.iter().map(|(k, v)| (k.as_str(), v)).collect();
+        bindings.sort_by_key(|&(k, _)| k);
+        bindings
+    }
+
+    /// Every line given to [`Session::eval`], in order.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Run a line, which is either `name = term` or a term. A term is an expression, a name
+    /// followed by primes and optionally a point to evaluate at like `f''(2)` or an expr to
+    /// compose with like `f(x + 1)`, or `simplify` or `derive` followed by a term. An expression that calls names at numbers, like
+    /// `f(2) * g'(3)`, is a value.
+    pub fn eval(&mut self, line: &str) -> Res<Reply> {
+        self.history.push(line.into());
+
+        if let Some((name, term)) = split_binding(line) {
+            if !is_ident(name)
+                || RESERVED.contains(&name)
+                || name == LAST
+                || self.functions.get(name).is_some()
+            {
+                return Err(ReplError::InvalidName(name.into()));
+            }
+
+            let offset = line.len() - term.len();
+            return match self.term(term, offset)? {
+                Term::Expr(expr) => {
+                    self.bindings.insert(name.into(), expr.clone());
+                    Ok(Reply::Bound(name.into(), expr))
+                }
+                Term::Value(v) => {
+                    self.bindings.insert(name.into(), Expr::Num(v));
+                    Ok(Reply::Bound(name.into(), Expr::Num(v)))
+                }
+            };
+        }
+
+        match self.term(line, 0)? {
+            Term::Expr(expr) => {
+                self.bindings.insert(LAST.into(), expr.clone());
+                Ok(Reply::Expr(expr))
+            }
+            Term::Value(v) => Ok(Reply::Value(v)),
+        }
+    }
+
+    fn term(&self, term: &str, offset: usize) -> Res<Term> {
+        let trimmed = term.trim_start();
+        let offset = offset + term.len() - trimmed.len();
+        let term = trimmed.trim_end();
+
+        if let Some(rest) = keyword(term, "simplify") {
+            return self
+                .term(rest, offset + term.len() - rest.len())
+                .map(|t| t.map(Expr::simplify));
+        }
+        if let Some(rest) = keyword(term, "derive") {
+            return self
+                .term(rest, offset + term.len() - rest.len())
+                .map(|t| t.map(Expr::derive));
+        }
+
+        match reference(term) {
+            Some((name, order, point))
+                if !RESERVED.contains(&name) && self.functions.get(name).is_none() =>
+            {
+                let mut expr = match self.bindings.get(name) {
+                    Some(expr) => expr.clone(),
+                    // an unknown function like `f'(x)`
+                    None if point.is_some() => return self.expression(term, offset),
+                    None => return Err(ReplError::Unbound(name.into())),
+                };
+                for _ in 0..order {
+                    expr = expr.derive();
+                }
+
+                match point {
+                    Some(point) => {
+                        // the name and primes can not contain a parenthesis
+                        let point = self.parse(point, offset + term.find('(').unwrap() + 1)?;
+                        Ok(if point.is_constant() {
+                            Term::Value(expr.evaluate(point.evaluate(0.0)))
+                        } else {
+                            Term::Expr(expr.compose(point))
+                        })
+                    }
+                    None => Ok(Term::Expr(expr)),
+                }
+            }
+            _ => self.expression(term, offset),
+        }
+    }
+
+    /// Parse an expression, which is a value when it calls a binding at a number like
+    /// `f(2) * g(3)`.
+    fn expression(&self, source: &str, offset: usize) -> Res<Term> {
+        let tokens = lex(source).map_err(|e| ReplError::Lang(e, offset))?;
+        let calls = tokens.iter().enumerate().any(|(i, t)| {
+            matches!(&t.token, TokenType::Ident(name) if self.bindings.contains_key(name))
+                && tokens[i + 1..]
+                    .iter()
+                    .find(|t| t.token != TokenType::Prime)
+                    .is_some_and(|t| t.token == TokenType::OpenParen)
+        });
+
+        let expr = parse_with(tokens, &self.bindings, &self.functions)
+            .map_err(|e| ReplError::Lang(e, offset))?;
+        Ok(if calls && expr.is_constant() {
+            Term::Value(expr.evaluate(0.0))
+        } else {
+            Term::Expr(expr)
+        })
+    }
+
+    fn parse(&self, source: &str, offset: usize) -> Res<Expr> {
+        lex(source)
+            .and_then(|tokens| parse_with(tokens, &self.bindings, &self.functions))
+            .map_err(|e| ReplError::Lang(e, offset))
+    }
+}
+
+enum Term {
+    Expr(Expr),
+    Value(f64),
+}
+
+impl Term {
+    fn map(self, f: impl FnOnce(Expr) -> Expr) -> Self {
+        match self {
+            Term::Expr(e) => Term::Expr(f(e)),
+            v => v,
+        }
+    }
+}
+
+/// Split `name = term`, the name is trimmed.
+fn split_binding(line: &str) -> Option<(&str, &str)> {
+    let (name, term) = line.split_once('=')?;
+    Some((name.trim(), term))
+}
+
+/// Strip a leading keyword followed by a space.
+fn keyword<'a>(term: &'a str, word: &str) -> Option<&'a str> {
+    term.strip_prefix(word)
+        .filter(|rest| rest.starts_with(char::is_whitespace))
+}
+
+/// Split `name''(point)` into the name, the number of primes and the point, when the
+/// parentheses hold the whole rest of the term.
+fn reference(term: &str) -> Option<(&str, usize, Option<&str>)> {
+    let end = term
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(term.len());
+    let (name, rest) = term.split_at(end);
+    if !is_ident(name) {
+        return None;
+    }
+
+    let primes = rest.len() - rest.trim_start_matches('\'').len();
+    let rest = rest[primes..].trim_start();
+
+    if rest.is_empty() {
+        return Some((name, primes, None));
+    }
+
+    let point = rest.strip_prefix('(')?.strip_suffix(')')?;
+    // `f(2) * g(3)` is not `f` at `2) * g(3`
+    balanced(point).then_some((name, primes, Some(point)))
+}
+
+/// Whether every parenthesis is closed after it is opened.
+fn balanced(s: &str) -> bool {
+    let mut depth = 0usize;
+    for c in s.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => match depth.checked_sub(1) {
+                Some(d) => depth = d,
+                None => return false,
+            },
+            _ => {}
+        }
+    }
+    depth == 0
+}
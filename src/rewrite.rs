@@ -0,0 +1,422 @@
+//! Rewrite exprs with rules where a hole like `?a` matches any expr.
+//!
+//! ```text
+//! ?a * (?b + ?c) -> ?a * ?b + ?a * ?c
+//! ?a * ?b -> ?b * ?a if ?b is num
+//! ?a / ?a -> 1 if ?a is nonzero
+//! ```
+//!
+//! A [`Rewriter`] applies its rules everywhere in an expr until none apply anymore.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::{
+    lang::{lex, parse_pattern, LangError, Token, TokenType},
+    Bin, Expr, Function, Trig,
+};
+
+/// The expr matched by every hole, by name without the `?`.
+pub type Bindings = HashMap<String, Expr>;
+
+/// An expr with holes.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Pattern {
+    /// Matches any expr, a hole used several times has to match the same expr everywhere.
+    Hole(String),
+    Bin(Bin, Box<Pattern>, Box<Pattern>),
+    Trig(Trig, Box<Pattern>),
+    Derivative(usize, Box<Pattern>),
+    Func(Arc<Function>, Box<Pattern>),
+    Unknown(String, usize, Box<Pattern>),
+    Param(String),
+    Var,
+    Num(f64),
+}
+
+impl Pattern {
+    /// Match an expr, adding the expr of every hole to `bindings`. The bindings are left
+    /// partially filled if the match fails.
+    pub fn matches(&self, expr: &Expr, bindings: &mut Bindings) -> bool {
+        match (self, expr) {
+            (Pattern::Hole(name), e) => match bindings.get(name) {
+                Some(bound) => bound == e,
+                None => {
+                    bindings.insert(name.clone(), e.clone());
+                    true
+                }
+            },
+            (Pattern::Bin(t, a, b), Expr::Bin(u, c, d)) => {
+                t == u && a.matches(c, bindings) && b.matches(d, bindings)
+            }
+            (Pattern::Trig(t, a), Expr::Trig(u, b)) => t == u && a.matches(b, bindings),
+            (Pattern::Derivative(n, a), Expr::Derivative(m, b)) => n == m && a.matches(b, bindings),
+            (Pattern::Func(f, a), Expr::Func(g, b)) => f == g && a.matches(b, bindings),
+            (Pattern::Unknown(f, n, a), Expr::Unknown(g, m, b)) => {
+                f == g && n == m && a.matches(b, bindings)
+            }
+            (Pattern::Param(a), Expr::Param(b)) => a == b,
+            (Pattern::Var, Expr::Var) => true,
+            (Pattern::Num(n), Expr::Num(m)) => n == m,
+            _ => false,
+        }
+    }
+
+    /// Replace every hole by its expr, `None` if a hole is not bound.
+    pub fn instantiate(&self, bindings: &Bindings) -> Option<Expr> {
+        let sub = |p: &Pattern| p.instantiate(bindings).map(Box::new);
+
+        Some(match self {
+            Pattern::Hole(name) => bindings.get(name)?.clone(),
+            Pattern::Bin(t, a, b) => Expr::Bin(*t, sub(a)?, sub(b)?),
+            Pattern::Trig(t, a) => Expr::Trig(*t, sub(a)?),
+            Pattern::Derivative(n, a) => Expr::Derivative(*n, sub(a)?),
+            Pattern::Func(f, a) => Expr::Func(f.clone(), sub(a)?),
+            Pattern::Unknown(f, n, a) => Expr::Unknown(f.clone(), *n, sub(a)?),
+            Pattern::Param(name) => Expr::Param(name.clone()),
+            Pattern::Var => Expr::Var,
+            Pattern::Num(n) => Expr::Num(*n),
+        })
+    }
+
+    /// Every hole of the pattern.
+    fn holes<'a>(&'a self, holes: &mut Vec<&'a str>) {
+        match self {
+            Pattern::Hole(name) => holes.push(name),
+            Pattern::Bin(_, a, b) => {
+                a.holes(holes);
+                b.holes(holes);
+            }
+            Pattern::Trig(_, a)
+            | Pattern::Derivative(_, a)
+            | Pattern::Func(_, a)
+            | Pattern::Unknown(_, _, a) => a.holes(holes),
+            Pattern::Param(_) | Pattern::Var | Pattern::Num(_) => {}
+        }
+    }
+
+    /// Turn a parsed pattern back into a pattern, the parser writes a hole as an unknown
+    /// function named `?a`.
+    fn from_parsed(expr: Expr) -> Self {
+        let sub = |e: Box<Expr>| Box::new(Pattern::from_parsed(*e));
+
+        match expr {
+            Expr::Unknown(name, 0, _) if name.starts_with('?') => Pattern::Hole(name[1..].into()),
+            Expr::Bin(t, a, b) => Pattern::Bin(t, sub(a), sub(b)),
+            Expr::Trig(t, a) => Pattern::Trig(t, sub(a)),
+            Expr::Derivative(n, a) => Pattern::Derivative(n, sub(a)),
+            Expr::Func(f, a) => Pattern::Func(f, sub(a)),
+            Expr::Unknown(f, n, a) => Pattern::Unknown(f, n, sub(a)),
+            Expr::Param(name) => Pattern::Param(name),
+            Expr::Var => Pattern::Var,
+            Expr::Num(n) => Pattern::Num(n),
+        }
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = LangError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_pattern(lex(s)?).map(Pattern::from_parsed)
+    }
+}
+
+/// A check on the matched exprs that has to pass for a rule to apply.
+#[derive(Clone)]
+pub enum Condition {
+    /// `?a is num`
+    Num(String),
+    /// `?a is const`, the expr does not depend on `x`.
+    Constant(String),
+    /// `?a is nonzero`, a number other than 0.
+    NonZero(String),
+    /// `?a is int`, a whole number.
+    Integer(String),
+    /// `?a is positive`, a number greater than 0.
+    Positive(String),
+    Custom(Arc<dyn Fn(&Bindings) -> bool + Send + Sync>),
+}
+
+impl Condition {
+    pub fn check(&self, bindings: &Bindings) -> bool {
+        match self {
+            Condition::Num(hole) => bindings.get(hole).is_some_and(Expr::is_num),
+            Condition::Constant(hole) => bindings.get(hole).is_some_and(Expr::is_constant),
+            Condition::NonZero(hole) => {
+                matches!(bindings.get(hole), Some(Expr::Num(n)) if *n != 0.0)
+            }
+            Condition::Integer(hole) => {
+                matches!(bindings.get(hole), Some(Expr::Num(n)) if n.fract() == 0.0)
+            }
+            Condition::Positive(hole) => {
+                matches!(bindings.get(hole), Some(Expr::Num(n)) if *n > 0.0)
+            }
+            Condition::Custom(check) => check(bindings),
+        }
+    }
+}
+
+impl Debug for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Condition::Num(hole) => write!(f, "?{hole} is num"),
+            Condition::Constant(hole) => write!(f, "?{hole} is const"),
+            Condition::NonZero(hole) => write!(f, "?{hole} is nonzero"),
+            Condition::Integer(hole) => write!(f, "?{hole} is int"),
+            Condition::Positive(hole) => write!(f, "?{hole} is positive"),
+            Condition::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// A rule `lhs -> rhs`, with conditions written after `if` and separated by `and`.
+#[derive(Clone, Debug)]
+pub struct Rule {
+    lhs: Pattern,
+    rhs: Pattern,
+    conditions: Vec<Condition>,
+}
+
+impl Rule {
+    /// # Panics
+    ///
+    /// If `rhs` uses a hole that is not in `lhs`.
+    pub fn new(lhs: Pattern, rhs: Pattern) -> Self {
+        if let Some(hole) = Self::unbound_hole(&lhs, &rhs) {
+            panic!("the hole ?{hole} is not in the left side of the rule");
+        }
+
+        Self {
+            lhs,
+            rhs,
+            conditions: vec![],
+        }
+    }
+
+    /// Add a condition to the rule.
+    pub fn when(mut self, condition: Condition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Rewrite the expr if the rule matches it, only the expr itself is matched and not its
+    /// parts.
+    pub fn apply(&self, expr: &Expr) -> Option<Expr> {
+        let mut bindings = Bindings::new();
+        if !self.lhs.matches(expr, &mut bindings) {
+            return None;
+        }
+        if !self.conditions.iter().all(|c| c.check(&bindings)) {
+            return None;
+        }
+
+        self.rhs.instantiate(&bindings)
+    }
+
+    fn unbound_hole<'a>(lhs: &Pattern, rhs: &'a Pattern) -> Option<&'a str> {
+        let (mut bound, mut used) = (vec![], vec![]);
+        lhs.holes(&mut bound);
+        rhs.holes(&mut used);
+        used.into_iter().find(|h| !bound.contains(h))
+    }
+}
+
+impl FromStr for Rule {
+    type Err = LangError;
+
+    /// The location of an error is a byte offset in the whole rule.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((lhs, rest)) = s.split_once("->") else {
+            return Err(LangError::UnexpectedToken(usize::MAX));
+        };
+        let rhs_offset = lhs.len() + 2;
+        let (rhs, conditions) = match rest.find(" if ") {
+            Some(i) => (&rest[..i], Some((&rest[i + 4..], rhs_offset + i + 4))),
+            None => (rest, None),
+        };
+
+        let lhs: Pattern = lhs.parse().map_err(|e: LangError| e.offset(0, lhs.len()))?;
+        let rhs: Pattern = rhs
+            .parse()
+            .map_err(|e: LangError| e.offset(rhs_offset, rhs.len()))?;
+
+        if let Some(hole) = Rule::unbound_hole(&lhs, &rhs) {
+            let loc = rhs_offset + rest.find(&format!("?{hole}")).unwrap();
+            return Err(LangError::InvalidWord(format!("?{hole}"), loc));
+        }
+
+        let mut rule = Rule {
+            lhs,
+            rhs,
+            conditions: vec![],
+        };
+        if let Some((source, offset)) = conditions {
+            rule.conditions =
+                parse_conditions(source).map_err(|e| e.offset(offset, source.len()))?;
+        }
+
+        Ok(rule)
+    }
+}
+
+/// Parse `?a is num and ?b is const`.
+fn parse_conditions(source: &str) -> Result<Vec<Condition>, LangError> {
+    let tokens = lex(source)?;
+    let mut conditions = vec![];
+    let mut tokens = tokens.iter();
+
+    loop {
+        let hole = match tokens.next() {
+            Some(Token {
+                token: TokenType::Hole(hole),
+                ..
+            }) => hole.clone(),
+            Some(t) => return Err(LangError::UnexpectedToken(t.loc)),
+            None => unreachable!(),
+        };
+
+        match tokens.next() {
+            Some(Token {
+                token: TokenType::Ident(is),
+                ..
+            }) if is == "is" => {}
+            Some(t) => return Err(LangError::UnexpectedToken(t.loc)),
+            None => unreachable!(),
+        }
+
+        conditions.push(match tokens.next() {
+            Some(Token {
+                token: TokenType::Ident(kind),
+                loc,
+            }) => match kind.as_str() {
+                "num" => Condition::Num(hole),
+                "const" => Condition::Constant(hole),
+                "nonzero" => Condition::NonZero(hole),
+                "int" => Condition::Integer(hole),
+                "positive" => Condition::Positive(hole),
+                _ => return Err(LangError::InvalidWord(kind.clone(), *loc)),
+            },
+            Some(t) => return Err(LangError::UnexpectedToken(t.loc)),
+            None => unreachable!(),
+        });
+
+        match tokens.next() {
+            Some(Token {
+                token: TokenType::END,
+                ..
+            }) => return Ok(conditions),
+            Some(Token {
+                token: TokenType::Ident(and),
+                ..
+            }) if and == "and" => {}
+            Some(t) => return Err(LangError::UnexpectedToken(t.loc)),
+            None => unreachable!(),
+        }
+    }
+}
+
+/// Rules that can not stop rewriting each other are stopped after this many passes.
+const MAX_PASSES: usize = 100;
+
+/// Apply `step` until the expr does not change, or [`MAX_PASSES`] times.
+pub(crate) fn fixed_point(mut expr: Expr, step: impl Fn(Expr) -> Expr) -> Expr {
+    for _ in 0..MAX_PASSES {
+        let next = step(expr.clone());
+        if same(&next, &expr) {
+            break;
+        }
+        expr = next;
+    }
+    expr
+}
+
+/// Whether two exprs are the same tree, where a NaN is the same as itself unlike with `==`.
+fn same(a: &Expr, b: &Expr) -> bool {
+    match (a, b) {
+        (Expr::Bin(t, a, b), Expr::Bin(u, c, d)) => t == u && same(a, c) && same(b, d),
+        (Expr::Trig(t, a), Expr::Trig(u, b)) => t == u && same(a, b),
+        (Expr::Derivative(n, a), Expr::Derivative(m, b)) => n == m && same(a, b),
+        (Expr::Func(f, a), Expr::Func(g, b)) => f == g && same(a, b),
+        (Expr::Unknown(f, n, a), Expr::Unknown(g, m, b)) => f == g && n == m && same(a, b),
+        (Expr::Num(a), Expr::Num(b)) => a.to_bits() == b.to_bits(),
+        (a, b) => a == b,
+    }
+}
+
+/// A set of rules applied everywhere in an expr until none apply.
+#[derive(Clone, Debug, Default)]
+pub struct Rewriter {
+    rules: Vec<Rule>,
+}
+
+impl Rewriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse one rule per line, empty lines and everything after a `#` are ignored. The
+    /// location of an error is a byte offset in the whole source.
+    pub fn parse(source: &str) -> Result<Self, LangError> {
+        let mut rewriter = Self::new();
+        let mut offset = 0;
+
+        for line in source.split_inclusive('\n') {
+            let code = line.split('#').next().unwrap().trim_end();
+            let line_offset = offset;
+            offset += line.len();
+
+            if code.trim().is_empty() {
+                continue;
+            }
+
+            let rule = code
+                .parse()
+                .map_err(|e: LangError| e.offset(line_offset, code.len()))?;
+            rewriter.add(rule);
+        }
+
+        Ok(rewriter)
+    }
+
+    /// Add a rule, the rules are tried in the order they are added.
+    pub fn add(&mut self, rule: Rule) {
+        self.rules.push(rule);
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Apply the rules until the expr does not change.
+    pub fn rewrite(&self, expr: Expr) -> Expr {
+        fixed_point(expr, |e| self.rewrite_once(e))
+    }
+
+    /// Apply the rules and [`Expr::simplify`] until the expr does not change, for rules that
+    /// need the constants to be folded to match.
+    pub fn rewrite_simplify(&self, expr: Expr) -> Expr {
+        fixed_point(expr.simplify(), |e| self.rewrite(e).simplify())
+    }
+
+    /// Rewrite the parts of the expr first, then the first rule that matches the expr itself.
+    fn rewrite_once(&self, expr: Expr) -> Expr {
+        let sub = |e: Box<Expr>| Box::new(self.rewrite_once(*e));
+
+        let expr = match expr {
+            Expr::Bin(t, a, b) => Expr::Bin(t, sub(a), sub(b)),
+            Expr::Trig(t, a) => Expr::Trig(t, sub(a)),
+            Expr::Derivative(n, a) => Expr::Derivative(n, sub(a)),
+            Expr::Func(f, a) => Expr::Func(f, sub(a)),
+            Expr::Unknown(f, n, a) => Expr::Unknown(f, n, sub(a)),
+            e => e,
+        };
+
+        self.rules
+            .iter()
+            .find_map(|rule| rule.apply(&expr))
+            .unwrap_or(expr)
+    }
+}
@@ -1,2 +1,53 @@
+mod analysis;
+mod codegen;
 mod deriver;
+mod display;
+mod dual;
+mod expand;
+mod format;
+mod function;
+mod integrate;
 mod lang;
+mod limit;
+mod logs;
+mod polynomial;
+mod pretty;
+mod quadrature;
+mod repl;
+mod rewrite;
+mod roots;
+mod simplify;
+mod substitute;
+mod tape;
+mod taylor;
+mod trig;
+
+use crate::{Expr, Polynomial};
+
+#[track_caller]
+fn parse(s: &str) -> Expr {
+    s.parse().unwrap()
+}
+
+#[track_caller]
+fn poly(s: &str) -> Polynomial {
+    Polynomial::try_from(&parse(s)).unwrap()
+}
+
+/// Whether two floats are equal up to the error of the numerical methods.
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+/// Assert that a pass keeps the value of the expr of every source at every point where it is
+/// defined.
+#[track_caller]
+fn assert_keeps_value(pass: impl Fn(Expr) -> Expr, sources: &[&str], points: &[f64]) {
+    for s in sources {
+        let f = parse(s);
+        let g = pass(f.clone());
+        for &x in points.iter().filter(|&&x| f.evaluate(x).is_finite()) {
+            assert!(close(f.evaluate(x), g.evaluate(x)), "{s} -> {g} at {x}");
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::f64::consts::PI;
+
+use super::{close, parse};
+use crate::{prelude::*, Concavity, Monotonicity, PointKind};
+
+#[test]
+fn critical_points() {
+    let points = parse("x^3 - 3 * x").critical_points(-3.0..=3.0);
+
+    assert_eq!(points.len(), 2);
+    assert!(close(points[0].x, -1.0) && close(points[0].value, 2.0));
+    assert_eq!(points[0].kind, PointKind::Maximum);
+    assert!(close(points[1].x, 1.0) && close(points[1].value, -2.0));
+    assert_eq!(points[1].kind, PointKind::Minimum);
+}
+
+#[test]
+fn flat() {
+    let points = Var.exp(4.0).critical_points(-1.0..=1.0);
+    assert_eq!(points.len(), 1);
+    assert_eq!(points[0].kind, PointKind::Minimum);
+
+    let points = Var.exp(3.0).critical_points(-1.0..=1.0);
+    assert_eq!(points.len(), 1);
+    assert!(close(points[0].x, 0.0));
+    assert_eq!(points[0].kind, PointKind::Inflection);
+}
+
+#[test]
+fn trig() {
+    let kinds: Vec<_> = Var
+        .trig(Sin)
+        .critical_points(0.0..=2.0 * PI)
+        .iter()
+        .map(|p| (p.kind, (p.x / PI * 2.0).round()))
+        .collect();
+    assert_eq!(
+        kinds,
+        [(PointKind::Maximum, 1.0), (PointKind::Minimum, 3.0)]
+    );
+}
+
+#[test]
+fn analyze() {
+    let analysis = parse("x^3 - 3 * x").analyze(-3.0..=3.0);
+
+    let monotonicity: Vec<_> = analysis
+        .monotonicity
+        .iter()
+        .map(|(r, m)| (r.start().round(), r.end().round(), *m))
+        .collect();
+    assert_eq!(
+        monotonicity,
+        [
+            (-3.0, -1.0, Monotonicity::Increasing),
+            (-1.0, 1.0, Monotonicity::Decreasing),
+            (1.0, 3.0, Monotonicity::Increasing)
+        ]
+    );
+
+    assert_eq!(analysis.inflection_points.len(), 1);
+    assert!(close(analysis.inflection_points[0], 0.0));
+    let concavity: Vec<_> = analysis.concavity.iter().map(|(_, c)| *c).collect();
+    assert_eq!(concavity, [Concavity::Down, Concavity::Up]);
+}
+
+#[test]
+fn merged() {
+    let analysis = Var.exp(3.0).analyze(-2.0..=2.0);
+
+    assert_eq!(
+        analysis.monotonicity,
+        [(-2.0..=2.0, Monotonicity::Increasing)]
+    );
+}
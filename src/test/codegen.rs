@@ -0,0 +1,246 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::prelude::*;
+
+/// Exprs that use every part of the generators.
+fn exprs() -> Vec<Expr> {
+    vec![
+        Var.exp(2.0).mul(Var.trig(Sin)),
+        Var.add(1.0).exp(3.0).div(Var.exp(-2.0)),
+        Num(-2.0).exp(Var).add(Num(3.0).ln()),
+        Expr::E.exp(Var.mul(2.0)).sub(Var.log(5.0)),
+        Var.log(10.0).add(Var.log(2.0)).add(Var.log(3.0)),
+        Var.trig(Sec).div(Var.add(1.0).trig(Cot)),
+        Var.mul(2.0).trig(Csc).add(Var.trig(Tan).derive()),
+        Var.exp(Var).add(Var.exp(20.0)),
+        Var.sub(Var.sub(Num(-1.0))),
+    ]
+}
+
+const POINTS: [f64; 4] = [0.5, 1.0, 2.0, 3.7];
+
+/// A file in the temp dir that is only used by this test.
+fn temp(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("stoat-{}-{name}", std::process::id()))
+}
+
+/// Compile a program that prints one number per line, then run it. `None` if the compiler
+/// can not be started.
+fn run(
+    source: &str,
+    name: &str,
+    compile: impl FnOnce(&Path, &Path) -> Command,
+) -> Option<Vec<f64>> {
+    let (path, exe) = (temp(name), temp(&format!("{name}.out")));
+    std::fs::write(&path, source).unwrap();
+
+    let compiled = compile(&path, &exe).output().ok()?;
+    assert!(
+        compiled.status.success(),
+        "{}\n{source}",
+        String::from_utf8_lossy(&compiled.stderr)
+    );
+    let output = Command::new(&exe).output().unwrap();
+    std::fs::remove_file(path).unwrap();
+    std::fs::remove_file(exe).unwrap();
+
+    Some(
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .lines()
+            .map(|l| l.parse().unwrap())
+            .collect(),
+    )
+}
+
+/// Check the values printed by a compiled program against the evaluated exprs.
+fn assert_values(values: &[f64]) {
+    let expected = exprs()
+        .iter()
+        .flat_map(|e| POINTS.map(|x| e.evaluate(x)))
+        .collect::<Vec<_>>();
+    assert_eq!(values.len(), expected.len());
+    for (v, e) in values.iter().zip(expected) {
+        assert!(
+            (v.is_nan() && e.is_nan()) || (v - e).abs() <= 1e-12 * e.abs().max(1.0),
+            "{v} != {e}"
+        );
+    }
+}
+
+mod rust {
+    use super::*;
+
+    #[test]
+    fn compiles() {
+        let mut source = String::new();
+        for (i, e) in exprs().iter().enumerate() {
+            source += &e.to_rust(&format!("f{i}"));
+        }
+        source += "fn main() {\n";
+        for i in 0..exprs().len() {
+            source += &format!("    for x in {POINTS:?} {{ println!(\"{{:?}}\", f{i}(x)); }}\n");
+        }
+        source += "}\n";
+
+        let rustc = std::env::var("RUSTC").unwrap_or("rustc".into());
+        let values = run(&source, "rust.rs", |path, exe| {
+            let mut command = Command::new(rustc);
+            command.arg(path).arg("-o").arg(exe);
+            command
+        })
+        .expect("rustc is needed to run the tests");
+        assert_values(&values);
+    }
+
+    #[test]
+    fn function() {
+        assert_eq!(
+            Var.exp(2.0).mul(Var.trig(Sin)).to_rust("f"),
+            "fn f(x: f64) -> f64 {\n    x.powi(2) * x.sin()\n}\n"
+        )
+    }
+
+    #[test]
+    fn derivative() {
+        assert_eq!(
+            Var.exp(3.0).derive().to_rust("df"),
+            "fn df(x: f64) -> f64 {\n    3.0 * x.powi(2)\n}\n"
+        )
+    }
+
+    #[test]
+    fn literal_receiver() {
+        assert_eq!(
+            Num(-2.0).exp(Var).add(Num(3.0).ln()).to_rust("f"),
+            "fn f(x: f64) -> f64 {\n    (-2.0_f64).powf(x) + 3.0_f64.ln()\n}\n"
+        )
+    }
+
+    #[test]
+    fn exp_log() {
+        assert_eq!(
+            Expr::E.exp(Var.mul(2.0)).sub(Var.log(5.0)).to_rust("f"),
+            "fn f(x: f64) -> f64 {\n    (x * 2.0).exp() - x.log(5.0)\n}\n"
+        )
+    }
+
+    #[test]
+    fn reciprocal_trig() {
+        assert_eq!(
+            Var.trig(Sec).div(Var.add(1.0).trig(Cot)).to_rust("f"),
+            "fn f(x: f64) -> f64 {\n    x.cos().recip() / (x + 1.0).tan().recip()\n}\n"
+        )
+    }
+
+    #[test]
+    fn associativity() {
+        assert_eq!(
+            Var.sub(Var.sub(Num(-1.0))).to_rust("f"),
+            "fn f(x: f64) -> f64 {\n    x - (x - -1.0)\n}\n"
+        )
+    }
+}
+
+mod c {
+    use super::*;
+
+    /// Skipped when there is no C compiler.
+    #[test]
+    fn compiles() {
+        let mut source = String::from("#include <math.h>\n#include <stdio.h>\n\n");
+        for (i, e) in exprs().iter().enumerate() {
+            source += &e.to_c(&format!("f{i}"));
+        }
+        source += "int main(void) {\n";
+        source += &format!(
+            "    double xs[] = {{{}}};\n",
+            POINTS.map(|x| format!("{x:?}")).join(", ")
+        );
+        for i in 0..exprs().len() {
+            source += &format!(
+                "    for (int j = 0; j < {}; j++) printf(\"%.17g\\n\", f{i}(xs[j]));\n",
+                POINTS.len()
+            );
+        }
+        source += "    return 0;\n}\n";
+
+        let values = run(&source, "c.c", |path, exe| {
+            let mut command = Command::new("cc");
+            command.arg(path).arg("-o").arg(exe).arg("-lm");
+            command
+        });
+        if let Some(values) = values {
+            assert_values(&values);
+        }
+    }
+
+    #[test]
+    fn function() {
+        assert_eq!(
+            Var.exp(2.0).mul(Var.trig(Sin)).to_c("f"),
+            "double f(double x) {\n    return x * x * sin(x);\n}\n"
+        )
+    }
+
+    #[test]
+    fn integer_power() {
+        assert_eq!(
+            Var.add(1.0).exp(3.0).div(Var.exp(-2.0)).to_c("f"),
+            "double f(double x) {\n    return (x + 1.0) * (x + 1.0) * (x + 1.0) / (1.0 / (x * x));\n}\n"
+        )
+    }
+
+    #[test]
+    fn pow() {
+        assert_eq!(
+            Var.exp(Var).add(Var.exp(20.0)).to_c("f"),
+            "double f(double x) {\n    return pow(x, x) + pow(x, 20.0);\n}\n"
+        )
+    }
+
+    #[test]
+    fn log() {
+        assert_eq!(
+            Var.log(10.0).add(Var.log(3.0)).to_c("f"),
+            "double f(double x) {\n    return log10(x) + log(x) / log(3.0);\n}\n"
+        )
+    }
+
+    #[test]
+    fn reciprocal_trig() {
+        assert_eq!(
+            Var.trig(Tan).derive().to_c("df"),
+            "double df(double x) {\n    return (1.0 / cos(x)) * (1.0 / cos(x)) * 1.0;\n}\n"
+        )
+    }
+}
+
+mod glsl {
+    use crate::prelude::*;
+
+    #[test]
+    fn function() {
+        assert_eq!(
+            Expr::E.exp(Var.neg()).to_glsl("f"),
+            "float f(float x) {\n    return exp(x * -1.0);\n}\n"
+        )
+    }
+
+    #[test]
+    fn log() {
+        assert_eq!(
+            Var.log(10.0).add(Var.log(2.0)).to_glsl("f"),
+            "float f(float x) {\n    return log(x) / log(10.0) + log2(x);\n}\n"
+        )
+    }
+
+    #[test]
+    fn csc() {
+        assert_eq!(
+            Var.mul(2.0).trig(Csc).to_glsl("f"),
+            "float f(float x) {\n    return 1.0 / sin(x * 2.0);\n}\n"
+        )
+    }
+}
@@ -93,7 +93,7 @@ mod basic {
     fn log_x_a_rule() {
         assert_eq!(
             Num(2.0).log(Var).derive(),
-            Num(2.0).ln().div(Var.mul(Var.ln().exp(2.0)))
+            Num(2.0).ln().neg().div(Var.mul(Var.ln().exp(2.0)))
         )
     }
 }
@@ -164,6 +164,19 @@ mod chain {
         )
     }
 
+    #[test]
+    fn log_f_a() {
+        let f = Var.exp(2.0);
+        assert_eq!(
+            Num(2.0).log(f.clone()).derive(),
+            Num(2.0)
+                .ln()
+                .neg()
+                .div(f.clone().mul(f.ln().exp(2.0)))
+                .mul(Num(2.0).mul(Var))
+        )
+    }
+
     #[test]
     fn log_f_g() {
         let f = Var.sprt();
@@ -181,3 +194,118 @@ mod chain {
         )
     }
 }
+
+mod evaluate {
+    use crate::prelude::*;
+
+    #[test]
+    fn borrowed() {
+        let expr = Var.exp(2.0).add(Var);
+        assert_eq!(expr.evaluate(2.0), 6.0);
+        assert_eq!(expr.evaluate(3.0), 12.0);
+    }
+
+    #[test]
+    fn reciprocal_trig() {
+        let x = 0.5f64;
+        assert_eq!(Var.trig(Csc).evaluate(x), x.sin().recip());
+        assert_eq!(Var.trig(Sec).evaluate(x), x.cos().recip());
+        assert_eq!(Var.trig(Cot).evaluate(x), x.cos() / x.sin());
+    }
+
+    #[test]
+    fn log() {
+        assert_eq!(Var.ln().evaluate(2.0), 2f64.ln());
+        assert_eq!(Var.log(2.0).evaluate(8.0), 3.0);
+    }
+}
+
+mod derivative {
+    use crate::prelude::*;
+
+    #[test]
+    fn expand() {
+        assert_eq!(
+            Var.exp(3.0).derivative(2).add(1.0).expand_derivatives(),
+            Var.exp(3.0).derive().derive().add(1.0)
+        )
+    }
+
+    #[test]
+    fn derive() {
+        assert_eq!(
+            Var.trig(Sin).derivative(1).derive(),
+            Var.trig(Sin).derive_n(2)
+        )
+    }
+
+    #[test]
+    fn evaluate() {
+        assert_eq!(Var.exp(3.0).derivative(2).evaluate(2.0), 12.0)
+    }
+}
+
+mod unknown {
+    use crate::prelude::*;
+
+    fn f() -> Expr {
+        Var.unknown("f", 0)
+    }
+
+    fn g() -> Expr {
+        Var.unknown("g", 0)
+    }
+
+    #[test]
+    fn derive() {
+        assert_eq!(f().derive_n(2), Var.unknown("f", 2))
+    }
+
+    #[test]
+    fn product_rule() {
+        assert_eq!(
+            f().mul(g()).derive(),
+            Var.unknown("f", 1)
+                .mul(g())
+                .add(Var.unknown("g", 1).mul(f()))
+        )
+    }
+
+    #[test]
+    fn chain_rule() {
+        assert_eq!(
+            Var.trig(Sin).unknown("f", 0).derive(),
+            Var.trig(Sin).unknown("f", 1).mul(Var.trig(Cos).mul(1.0))
+        )
+    }
+
+    #[test]
+    fn compose() {
+        assert_eq!(
+            f().compose(Var.exp(2.0)).derive(),
+            Var.exp(2.0).unknown("f", 1).mul(Num(2.0).mul(Var))
+        )
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            f().mul(g()).derive().to_string(),
+            "f'(x) * g(x) + g'(x) * f(x)"
+        );
+        assert_eq!(
+            Var.add(1.0).unknown("f", 2).exp(2.0).to_string(),
+            "f''(x + 1)^2"
+        );
+        assert_eq!(
+            Var.unknown("f", 1).latex().to_string(),
+            "f'\\left(x\\right)"
+        );
+    }
+
+    #[test]
+    fn evaluate() {
+        assert!(f().evaluate(1.0).is_nan());
+        assert!(!Num(2.0).unknown("f", 0).is_constant());
+    }
+}
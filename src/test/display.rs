@@ -0,0 +1,157 @@
+use crate::prelude::*;
+
+#[test]
+fn sub_right() {
+    assert_eq!(Var.sub(Var.sub(1.0)).to_string(), "x - (x - 1)")
+}
+
+#[test]
+fn sub_left() {
+    assert_eq!(Var.sub(Var).sub(1.0).to_string(), "x - x - 1")
+}
+
+#[test]
+fn div_right() {
+    assert_eq!(Var.div(Var.mul(2.0)).to_string(), "x / (x * 2)")
+}
+
+#[test]
+fn exp_right() {
+    assert_eq!(Var.exp(Var.exp(2.0)).to_string(), "x^(x^2)")
+}
+
+#[test]
+fn exp_left() {
+    assert_eq!(Var.exp(Var).exp(2.0).to_string(), "(x^x)^2")
+}
+
+#[test]
+fn negative() {
+    assert_eq!(Var.neg().to_string(), "x * (-1)")
+}
+
+#[test]
+fn negative_leading() {
+    assert_eq!(Num(-1.0).mul(Var).to_string(), "-1 * x")
+}
+
+#[test]
+fn negative_exp() {
+    assert_eq!(Num(-2.0).exp(Num(-1.0)).to_string(), "(-2)^(-1)")
+}
+
+#[test]
+fn func_arg() {
+    assert_eq!(
+        Var.mul(2.0).trig(Sin).trig(Cos).to_string(),
+        "cos (sin (x * 2))"
+    )
+}
+
+#[test]
+fn func_arg_exp() {
+    assert_eq!(Var.add(1.0).exp(2.0).ln().to_string(), "ln (x + 1)^2")
+}
+
+#[test]
+fn derivative() {
+    assert_eq!(Var.exp(2.0).derivative(1).to_string(), "d/dx x^2")
+}
+
+#[test]
+fn derivative_order() {
+    assert_eq!(
+        Var.mul(Var.trig(Sin)).derivative(2).to_string(),
+        "d^2/dx^2 (x * sin x)"
+    )
+}
+
+#[test]
+fn log_base() {
+    assert_eq!(Var.log(Var.exp(2.0)).to_string(), "log_x^2 x")
+}
+
+/// A small xorshift generator, so random exprs are the same on every run.
+pub(super) struct Rng(pub(super) u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    pub(super) fn expr(&mut self, depth: usize) -> Expr {
+        self.tree(depth, false)
+    }
+
+    /// An expr that can also hold the params `$a` and `$b`.
+    fn expr_with_params(&mut self, depth: usize) -> Expr {
+        self.tree(depth, true)
+    }
+
+    fn tree(&mut self, depth: usize, params: bool) -> Expr {
+        if depth == 0 || self.below(4) == 0 {
+            return match self.below(if params { 6 } else { 5 }) {
+                0 => Var,
+                1 => Expr::E,
+                2 => Num(self.below(20) as f64),
+                3 => Num(-(self.below(20) as f64)),
+                4 => Num((self.next() as i64 as f64) / 1e15),
+                _ => Expr::param(["a", "b"][self.below(2) as usize]).unwrap(),
+            };
+        }
+
+        match self.below(10) {
+            0 => self
+                .tree(depth - 1, params)
+                .add(self.tree(depth - 1, params)),
+            1 => self
+                .tree(depth - 1, params)
+                .sub(self.tree(depth - 1, params)),
+            2 => self
+                .tree(depth - 1, params)
+                .mul(self.tree(depth - 1, params)),
+            3 => self
+                .tree(depth - 1, params)
+                .div(self.tree(depth - 1, params)),
+            4 => self
+                .tree(depth - 1, params)
+                .exp(self.tree(depth - 1, params)),
+            5 => self
+                .tree(depth - 1, params)
+                .log(self.tree(depth - 1, params)),
+            6 => self.tree(depth - 1, params).ln(),
+            7 => self
+                .tree(depth - 1, params)
+                .derivative(1 + self.below(3) as usize),
+            8 => self
+                .tree(depth - 1, params)
+                .unknown("f", self.below(3) as usize),
+            _ => self.tree(depth - 1, params).trig(match self.below(6) {
+                0 => Sin,
+                1 => Cos,
+                2 => Tan,
+                3 => Csc,
+                4 => Sec,
+                _ => Cot,
+            }),
+        }
+    }
+}
+
+#[test]
+fn round_trip() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..5000 {
+        let expr = rng.expr_with_params(6);
+        let printed = expr.to_string();
+        assert_eq!(printed.parse::<Expr>(), Ok(expr), "{printed}");
+    }
+}
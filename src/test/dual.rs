@@ -0,0 +1,65 @@
+use crate::{prelude::*, Dual, Registry, Scalar};
+
+use super::{display::Rng, parse};
+
+#[test]
+fn arithmetic() {
+    let (a, b) = (Dual::new(2.0, 1.0), Dual::new(3.0, 0.5));
+    assert_eq!(a + b, Dual::new(5.0, 1.5));
+    assert_eq!(a - b, Dual::new(-1.0, 0.5));
+    assert_eq!(a * b, Dual::new(6.0, 4.0));
+    assert_eq!(a / b, Dual::new(2.0 / 3.0, (3.0 - 1.0) / 9.0));
+    assert_eq!(-a, Dual::new(-2.0, -1.0));
+}
+
+#[test]
+fn evaluate() {
+    assert_eq!(parse("x^3").evaluate_dual(2.0), Dual::new(8.0, 12.0));
+    assert_eq!(parse("x^2").evaluate_dual(-3.0), Dual::new(9.0, -6.0));
+    assert_eq!(Var.trig(Sin).evaluate_dual(0.0), Dual::new(0.0, 1.0));
+    assert_eq!(Var.ln().evaluate_dual(2.0), Dual::new(2f64.ln(), 0.5));
+}
+
+#[test]
+fn function() {
+    let functions = Registry::standard();
+    let sigmoid = functions.get("sigmoid").unwrap();
+    let dual = Var.func(sigmoid).evaluate_dual(0.0);
+    assert_eq!(dual, Dual::new(0.5, 0.25));
+}
+
+#[test]
+fn generic() {
+    fn twice<S: Scalar>(expr: &Expr, x: S) -> S {
+        expr.evaluate_scalar(x) + expr.evaluate_scalar(x)
+    }
+
+    let f = parse("x * e^x");
+    assert_eq!(twice(&f, 1.0), 2.0 * std::f64::consts::E);
+    assert_eq!(twice(&f, Dual::var(0.0)), Dual::new(0.0, 2.0));
+}
+
+#[test]
+fn matches_derive() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+    let mut compared = 0;
+    for _ in 0..2000 {
+        let expr = rng.expr(4);
+        let derivative = expr.clone().derive();
+        for x in [-1.7, 0.3, 2.2] {
+            let dual = expr.evaluate_dual(x);
+            let expected = derivative.evaluate(x);
+            assert_eq!(dual.value.to_bits(), expr.evaluate(x).to_bits(), "{expr}");
+            if expected.is_finite() && dual.derivative.is_finite() && expected.abs() < 1e6 {
+                compared += 1;
+                assert!(
+                    (dual.derivative - expected).abs() <= 1e-6 * (1.0 + expected.abs()),
+                    "d/dx {expr} at {x}: {} != {expected}",
+                    dual.derivative
+                );
+            }
+        }
+    }
+    assert!(compared > 1000);
+}
@@ -0,0 +1,89 @@
+use super::{assert_keeps_value, parse};
+use crate::prelude::*;
+
+#[test]
+fn distribute() {
+    assert_eq!(
+        parse("2 * (x + 1)").expand(),
+        Num(2.0).mul(Var).add(Num(2.0).mul(1.0))
+    );
+    assert_eq!(
+        parse("(x - 1) * sin x").expand(),
+        Var.mul(Var.trig(Sin)).sub(Num(1.0).mul(Var.trig(Sin)))
+    );
+}
+
+#[test]
+fn divide() {
+    assert_eq!(
+        parse("(x + 1) / 2").expand(),
+        Var.div(2.0).add(Num(1.0).div(2.0))
+    );
+}
+
+#[test]
+fn power() {
+    assert_eq!(
+        parse("(x + 1)^2").expand(),
+        Var.mul(Var)
+            .add(Var.mul(1.0))
+            .add(Num(1.0).mul(Var).add(Num(1.0).mul(1.0)))
+    );
+    assert_eq!(parse("(x + 1)^0.5").expand(), parse("(x + 1)^0.5"));
+}
+
+#[test]
+fn collect() {
+    assert_eq!(
+        parse("(x + 1)^3").expand().collect().to_string(),
+        "x^3 + 3 * x^2 + 3 * x + 1"
+    );
+    assert_eq!(
+        parse("(x - 1) * (x + 1)").expand().collect().to_string(),
+        "x^2 - 1"
+    );
+}
+
+#[test]
+fn collect_other() {
+    assert_eq!(
+        parse("x * sin x + 2 - sin x * x + cos x")
+            .collect()
+            .to_string(),
+        "2 + cos x"
+    );
+    assert_eq!(parse("x - x").collect(), Num(0.0));
+}
+
+#[test]
+fn derivative() {
+    assert_eq!(
+        parse("x^3 + x^2 + x")
+            .derive()
+            .expand()
+            .collect()
+            .to_string(),
+        "3 * x^2 + 2 * x + 1"
+    );
+    assert_eq!(
+        parse("(x^2 + 1) * (x - 3)")
+            .derive()
+            .expand()
+            .collect()
+            .to_string(),
+        "3 * x^2 - 6 * x + 1"
+    );
+}
+
+#[test]
+fn expansion_keeps_value() {
+    assert_keeps_value(
+        |f| f.expand().collect(),
+        &[
+            "(2x - 3)^4 / 5",
+            "(x + sin x) * (x - 1) + x (x + 2)^2",
+            "(x^2 + 1) * (x^0.5 - 3) - ln x",
+        ],
+        &[0.3, 1.7, 4.0],
+    );
+}
@@ -0,0 +1,155 @@
+use std::f64::consts::LN_2;
+
+use crate::{prelude::*, Notation, NumFormat};
+
+#[test]
+fn precision() {
+    assert_eq!(format!("{:.3}", Var.mul(0.1 + 0.2)), "x * 0.3")
+}
+
+#[test]
+fn digits() {
+    let format = NumFormat {
+        digits: Some(4),
+        ..Default::default()
+    };
+    assert_eq!(Var.mul(LN_2).display_with(format).to_string(), "x * 0.6931")
+}
+
+#[test]
+fn auto_scientific() {
+    let format = NumFormat {
+        digits: Some(3),
+        ..Default::default()
+    };
+    assert_eq!(
+        Num(123456789e10).display_with(format).to_string(),
+        "1.23e18"
+    )
+}
+
+#[test]
+fn auto_shortest() {
+    let text = |n: f64| Num(n).display_with(NumFormat::default()).to_string();
+    assert_eq!(text(1e-30), "1e-30");
+    assert_eq!(text(-2.5e20), "-2.5e20");
+    assert_eq!(text(0.00125), "0.00125");
+    assert_eq!(text(0.0), "0")
+}
+
+#[test]
+fn display_fixed() {
+    assert_eq!(Var.mul(1e-30).to_string(), format!("x * {}", 1e-30))
+}
+
+#[test]
+fn fixed() {
+    let format = NumFormat {
+        digits: Some(3),
+        notation: Notation::Fixed,
+        ..Default::default()
+    };
+    assert_eq!(Num(2.5).display_with(format).to_string(), "2.50")
+}
+
+#[test]
+fn scientific() {
+    let format = NumFormat {
+        notation: Notation::Scientific,
+        ..Default::default()
+    };
+    assert_eq!(Num(1200.0).display_with(format).to_string(), "1.2e3")
+}
+
+#[test]
+fn fraction() {
+    let format = NumFormat {
+        fractions: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        Var.exp(1.0 / 3.0).display_with(format).to_string(),
+        "x^(1/3)"
+    )
+}
+
+#[test]
+fn fraction_negative() {
+    let format = NumFormat {
+        fractions: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        Var.add(-0.75).display_with(format).to_string(),
+        "x + (-3/4)"
+    )
+}
+
+#[test]
+fn fraction_near_integer() {
+    let format = NumFormat {
+        fractions: true,
+        ..Default::default()
+    };
+    assert_eq!(Num(1e-20).display_with(format).to_string(), "1e-20");
+    assert_eq!(
+        Num(1.0000000000001).display_with(format).to_string(),
+        "1.0000000000001"
+    );
+    assert_eq!(Num(2.5e-7).display_with(format).to_string(), "2.5e-7")
+}
+
+#[test]
+fn symbolic_ln() {
+    let format = NumFormat {
+        symbolic_ln: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        Num(2.0).exp(Var).mul(LN_2).display_with(format).to_string(),
+        "2^x * ln 2"
+    )
+}
+
+mod latex {
+    use std::f64::consts::LN_2;
+
+    use crate::{prelude::*, NumFormat};
+
+    #[test]
+    fn simple() {
+        assert_eq!(
+            Var.exp(2.0).mul(Var.trig(Sin)).latex().to_string(),
+            "x^{2} \\cdot \\sin x"
+        )
+    }
+
+    #[test]
+    fn frac() {
+        assert_eq!(
+            Var.add(1.0).div(Var.sprt()).latex().to_string(),
+            "\\frac{x + 1}{\\sqrt{x}}"
+        )
+    }
+
+    #[test]
+    fn log() {
+        assert_eq!(
+            Var.add(1.0).log(2.0).latex().to_string(),
+            "\\log_{2}\\left(x + 1\\right)"
+        )
+    }
+
+    #[test]
+    fn format() {
+        let format = NumFormat {
+            fractions: true,
+            symbolic_ln: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            Var.mul(0.5).add(LN_2).latex_with(format).to_string(),
+            "x \\cdot \\frac{1}{2} + \\ln 2"
+        )
+    }
+}
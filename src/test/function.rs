@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use crate::lang::LangError;
+use crate::prelude::*;
+use crate::{Function, Registry};
+
+fn cube() -> Arc<Function> {
+    Function::new("cube", |x| x * x * x, |_| Num(3.0).mul(Var.exp(2.0)))
+}
+
+#[test]
+fn evaluate() {
+    assert_eq!(Var.add(1.0).func(&cube()).evaluate(1.0), 8.0);
+}
+
+#[test]
+fn derive() {
+    assert_eq!(Var.func(&cube()).derive(), Num(3.0).mul(Var.exp(2.0)));
+}
+
+#[test]
+fn chain_rule() {
+    assert_eq!(
+        Var.trig(Sin).func(&cube()).derive(),
+        Num(3.0)
+            .mul(Var.trig(Sin).exp(2.0))
+            .mul(Var.trig(Cos).mul(1.0))
+    );
+}
+
+#[test]
+fn register() {
+    let mut functions = Registry::new();
+    functions.register("cube", |x| x * x * x, "3x^2").unwrap();
+
+    assert_eq!(
+        functions.parse("cube 2x").unwrap(),
+        Num(2.0).mul(Var).func(&cube())
+    );
+}
+
+#[test]
+fn register_self() {
+    let mut functions = Registry::new();
+    let exp = functions.register("myexp", f64::exp, "myexp x").unwrap();
+
+    assert_eq!(Var.func(&exp).derive_n(3), Var.func(&exp));
+}
+
+#[test]
+fn no_cycle() {
+    let mut functions = Registry::standard();
+    let exp = Arc::downgrade(&functions.register("myexp", f64::exp, "myexp x").unwrap());
+    let sigmoid = Arc::downgrade(functions.get("sigmoid").unwrap());
+
+    drop(functions);
+    assert!(sigmoid.upgrade().is_none());
+    assert!(exp.upgrade().is_none());
+}
+
+#[test]
+fn derivative_of_self() {
+    let sigmoid = Function::new(
+        "sigmoid",
+        |x| 1.0 / (1.0 + (-x).exp()),
+        |s| s.clone().mul(Num(1.0).sub(s)),
+    );
+    let s = Var.func(&sigmoid);
+
+    assert_eq!(sigmoid.derivative(), s.clone().mul(Num(1.0).sub(s)));
+}
+
+#[test]
+fn register_invalid() {
+    let mut functions = Registry::new();
+
+    assert_eq!(
+        functions.register("sin", f64::sin, "cos x"),
+        Err(LangError::InvalidWord("sin".into(), 0))
+    );
+    assert!(functions.register("f", f64::sin, "cos (x").is_err());
+    assert!(functions.get("f").is_none());
+}
+
+#[test]
+fn unregistered() {
+    assert_eq!(
+        "cube x".parse::<Expr>(),
+        Err(LangError::InvalidWord("cube".into(), 0))
+    );
+}
+
+#[test]
+fn display() {
+    let f = Var.mul(2.0).func(&cube()).add(Var.func(&cube()));
+
+    assert_eq!(f.to_string(), "cube (x * 2) + cube x");
+    assert_eq!(
+        Registry::standard()
+            .parse("sigmoid x")
+            .unwrap()
+            .latex()
+            .to_string(),
+        "\\operatorname{sigmoid} x"
+    );
+}
+
+#[test]
+fn simplify() {
+    assert_eq!(Num(2.0).func(&cube()).simplify(), Num(8.0));
+}
+
+#[test]
+fn standard() {
+    let functions = Registry::standard();
+
+    let erf = functions.get("erf").unwrap();
+    assert!((erf.eval(1.0) - 0.8427007929497149).abs() < 1e-15);
+    assert!((erf.eval(2.5) - 0.999593047982555).abs() < 1e-15);
+    assert!((erf.eval(-3.0) + 0.9999779095030014).abs() < 1e-15);
+    assert_eq!(erf.eval(0.0), 0.0);
+
+    // every derivative is checked against a central difference
+    for name in ["sigmoid", "softplus", "erf"] {
+        let f = functions.parse(&format!("{name} (x^2)")).unwrap();
+        let d = f.clone().derive();
+        for x in [-2.0, -0.5, 0.3, 1.7] {
+            let h = 1e-6;
+            let numeric = (f.evaluate(x + h) - f.evaluate(x - h)) / (2.0 * h);
+            assert!((d.evaluate(x) - numeric).abs() < 1e-6, "{name} at {x}");
+        }
+    }
+}
+
+#[test]
+fn codegen() {
+    let f = Var.add(1.0).func(&cube());
+
+    assert_eq!(
+        f.to_rust("f"),
+        "fn f(x: f64) -> f64 {\n    cube(x + 1.0)\n}\n"
+    );
+    assert_eq!(
+        f.to_c("f"),
+        "double f(double x) {\n    return cube(x + 1.0);\n}\n"
+    );
+}
@@ -0,0 +1,100 @@
+use super::parse;
+use crate::Polynomial;
+
+fn integrate(s: &str) -> String {
+    parse(s).integrate().unwrap().to_string()
+}
+
+/// The antiderivative derives back to the expr.
+fn check(s: &str) {
+    let f = parse(s);
+    let integral = f
+        .integrate()
+        .unwrap_or_else(|| panic!("no antiderivative for {s}"));
+    let derivative = integral.clone().derive();
+    for x in [0.2, 0.7, 1.1, 1.9] {
+        let (a, b) = (f.evaluate(x), derivative.evaluate(x));
+        assert!(
+            (a - b).abs() < 1e-6 * (1.0 + a.abs()),
+            "d/dx ({integral}) = {b} but {s} = {a} at {x}"
+        );
+    }
+}
+
+#[test]
+fn power() {
+    assert_eq!(integrate("3"), "3 * x");
+    assert_eq!(integrate("x^3 + 2 * x"), "0.25 * x^4 + x^2");
+    assert_eq!(integrate("1 / x"), "ln x");
+    assert_eq!(integrate("x^-2"), "-1 * x^(-1)");
+    assert_eq!(integrate("x^2 / x"), "0.5 * x^2");
+    check("x^0.5");
+}
+
+#[test]
+fn table() {
+    assert_eq!(integrate("e^x"), "e^x");
+    assert_eq!(integrate("ln x"), "x * ln x - x");
+    assert_eq!(integrate("sin x"), "-1 * cos x");
+    assert_eq!(integrate("cos x"), "sin x");
+    assert_eq!(integrate("(sec x)^2"), "tan x");
+    assert_eq!(integrate("sec x * tan x"), "sec x");
+    for s in [
+        "2^x",
+        "log_2 x",
+        "tan x",
+        "cot x",
+        "sec x",
+        "csc x",
+        "(csc x)^2",
+        "csc x * cot x",
+    ] {
+        check(s);
+    }
+}
+
+#[test]
+fn linearity() {
+    assert_eq!(integrate("3 * cos x - e^x"), "3 * sin x - e^x");
+    check("(sin x + x^2) / 4");
+}
+
+#[test]
+fn substitution() {
+    assert_eq!(integrate("sin (2 * x + 1)"), "-0.5 * cos (2 * x + 1)");
+    assert_eq!(integrate("2 * x / (x^2 + 1)"), "ln (x^2 + 1)");
+    assert_eq!(integrate("x * e^(x^2)"), "0.5 * e^(x^2)");
+    assert_eq!(integrate("ln x / x"), "0.5 * (ln x)^2");
+    for s in [
+        "2 * x * cos (x^2)",
+        "(sin x)^2 * cos x",
+        "cos x / sin x",
+        "1 / (2 * x + 1)",
+    ] {
+        check(s);
+    }
+}
+
+#[test]
+fn parts() {
+    assert_eq!(integrate("x * e^x"), "x * e^x - e^x");
+    for s in ["x * sin x", "x^2 * e^x", "x^3 * cos x", "x * ln x"] {
+        check(s);
+    }
+}
+
+#[test]
+fn not_found() {
+    assert_eq!(parse("e^(x^2)").integrate(), None);
+    assert_eq!(parse("sin (x^2) * cos x").integrate(), None);
+    assert_eq!(parse("e^x * sin x").integrate(), None);
+}
+
+#[test]
+fn polynomial() {
+    assert_eq!(
+        Polynomial::new([1.0, 2.0, 3.0]).integrate(),
+        Polynomial::new([0.0, 1.0, 1.0, 1.0])
+    );
+    assert_eq!(Polynomial::default().integrate(), Polynomial::default());
+}
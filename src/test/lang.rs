@@ -1,2 +1,3 @@
 mod lex;
 mod parse;
+mod script;
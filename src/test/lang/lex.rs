@@ -62,6 +62,21 @@ fn symbol() {
     )
 }
 
+#[test]
+fn ident() {
+    let src = "let g = x";
+    assert_eq!(
+        lex(src).unwrap(),
+        vec![
+            TokenType::Ident("let".into()).at(0),
+            TokenType::Ident("g".into()).at(4),
+            TokenType::Equal.at(6),
+            TokenType::Var.at(8),
+            Token::end()
+        ]
+    )
+}
+
 mod error {
     use crate::lang::{lex, LangError};
 
@@ -102,3 +102,129 @@ fn juxta_paren() {
         Expr::Num(5.0).mul(Expr::Num(4.0).mul(Expr::Var))
     )
 }
+
+#[test]
+fn ln() {
+    assert_eq!("ln x".parse::<Expr>().unwrap(), Expr::Var.ln())
+}
+
+#[test]
+fn e() {
+    assert_eq!("e^x".parse::<Expr>().unwrap(), Expr::E.exp(Expr::Var))
+}
+
+#[test]
+fn negative() {
+    assert_eq!(
+        "x * -2".parse::<Expr>().unwrap(),
+        Expr::Var.mul(Expr::Num(-2.0))
+    )
+}
+
+#[test]
+fn negative_sub() {
+    assert_eq!(
+        "x - -2".parse::<Expr>().unwrap(),
+        Expr::Var.sub(Expr::Num(-2.0))
+    )
+}
+
+#[test]
+fn unknown() {
+    assert_eq!(
+        "f(x) g'(2x)".parse::<Expr>().unwrap(),
+        Expr::Var
+            .unknown("f", 0)
+            .mul(Expr::Num(2.0).mul(Expr::Var).unknown("g", 1))
+    )
+}
+
+#[test]
+fn unknown_prime() {
+    assert_eq!(
+        "f(x)'".parse::<Expr>().unwrap(),
+        Expr::Var.unknown("f", 0).derivative(1)
+    )
+}
+
+mod error {
+    use crate::{lang::LangError, Expr};
+
+    #[test]
+    fn unexpected_end() {
+        let err = "sin x +".parse::<Expr>().unwrap_err();
+        assert_eq!(err, LangError::UnexpectedToken(usize::MAX));
+        assert_eq!(err.to_string(), "unexpected end of input")
+    }
+
+    #[test]
+    fn derivative_order() {
+        assert_eq!(
+            "d^2/dx^3 x".parse::<Expr>(),
+            Err(LangError::InvalidDerivative(6))
+        )
+    }
+
+    #[test]
+    fn unknown_word() {
+        assert_eq!(
+            "2 * g".parse::<Expr>(),
+            Err(LangError::InvalidWord("g".into(), 4))
+        )
+    }
+
+    #[test]
+    fn unknown_unclosed() {
+        assert_eq!(
+            "f''(x + 1".parse::<Expr>(),
+            Err(LangError::UncloseParen(usize::MAX))
+        )
+    }
+
+    #[test]
+    fn trailing() {
+        assert_eq!("x )".parse::<Expr>(), Err(LangError::UnexpectedToken(2)))
+    }
+
+    #[test]
+    fn loc() {
+        let err = "x * )".parse::<Expr>().unwrap_err();
+        assert_eq!(err.loc(), Some(4));
+        assert_eq!(err.to_string(), "unexpected token")
+    }
+}
+
+#[test]
+fn leibniz() {
+    assert_eq!(
+        "d/dx x^2 sin x".parse::<Expr>().unwrap(),
+        Expr::Var
+            .exp(2.0)
+            .mul(Expr::Var.trig(Trig::Sin))
+            .derivative(1)
+    )
+}
+
+#[test]
+fn leibniz_order() {
+    assert_eq!(
+        "d^2/dx^2 (x^3)".parse::<Expr>().unwrap(),
+        Expr::Var.exp(3.0).derivative(2)
+    )
+}
+
+#[test]
+fn prime() {
+    assert_eq!(
+        "(x^3)''".parse::<Expr>().unwrap(),
+        Expr::Var.exp(3.0).derivative(2)
+    )
+}
+
+#[test]
+fn prime_juxta() {
+    assert_eq!(
+        "2(x^3)'".parse::<Expr>().unwrap(),
+        Expr::Num(2.0).mul(Expr::Var.exp(3.0).derivative(1))
+    )
+}
@@ -0,0 +1,87 @@
+use crate::{
+    lang::{run_script, LangError, Script, ScriptOutput},
+    prelude::*,
+};
+
+#[test]
+fn single() {
+    assert_eq!(
+        run_script("x^2"),
+        Ok(vec![ScriptOutput {
+            line: 1,
+            expr: Var.exp(2.0)
+        }])
+    )
+}
+
+#[test]
+fn binding() {
+    let src = "\
+# define g
+let g = x^2 + 1
+
+g * 2 # double it
+";
+    assert_eq!(
+        run_script(src),
+        Ok(vec![ScriptOutput {
+            line: 4,
+            expr: Var.exp(2.0).add(1.0).mul(2.0)
+        }])
+    )
+}
+
+#[test]
+fn derived() {
+    assert_eq!(
+        run_script("let g = x^3\nd/dx g"),
+        Ok(vec![ScriptOutput {
+            line: 2,
+            expr: Var.exp(3.0).derive()
+        }])
+    )
+}
+
+#[test]
+fn evaluated() {
+    assert_eq!(
+        run_script("let a = 2\na * 3 + 1"),
+        Ok(vec![ScriptOutput {
+            line: 2,
+            expr: Num(7.0)
+        }])
+    )
+}
+
+#[test]
+fn kept() {
+    let mut script = Script::new();
+    script.run("let g = sin x").unwrap();
+    script.run("let h = g^2").unwrap();
+    assert_eq!(script.get("h"), Some(&Var.trig(Sin).exp(2.0)))
+}
+
+mod error {
+    use super::*;
+
+    #[test]
+    fn unknown() {
+        assert_eq!(
+            run_script("let g = x\nf + 1"),
+            Err(LangError::InvalidWord("f".into(), 10))
+        )
+    }
+
+    #[test]
+    fn missing_equal() {
+        assert_eq!(run_script("let g x"), Err(LangError::UnexpectedToken(6)))
+    }
+
+    #[test]
+    fn keyword_name() {
+        assert_eq!(
+            run_script("x\nlet sin = x"),
+            Err(LangError::UnexpectedToken(6))
+        )
+    }
+}
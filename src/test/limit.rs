@@ -0,0 +1,133 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use super::{close, parse};
+use crate::{Direction, Limit};
+
+fn limit(s: &str, point: f64, direction: Direction) -> Limit {
+    parse(s).limit(point, direction)
+}
+
+fn finite(limit: Limit) -> f64 {
+    match limit {
+        Limit::Finite(v) => v,
+        l => panic!("{l:?} is not finite"),
+    }
+}
+
+#[test]
+fn continuous() {
+    assert_eq!(limit("x^2 + 1", 2.0, Direction::Both), Limit::Finite(5.0));
+    assert_eq!(limit("sin x", 0.0, Direction::Left), Limit::Finite(0.0));
+}
+
+#[test]
+fn lhopital() {
+    assert!(close(finite(limit("sin x / x", 0.0, Direction::Both)), 1.0));
+    assert!(close(
+        finite(limit("(x^2 - 1) / (x - 1)", 1.0, Direction::Both)),
+        2.0
+    ));
+    assert!(close(
+        finite(limit("(1 - cos x) / x^2", 0.0, Direction::Both)),
+        0.5
+    ));
+    assert!(close(
+        finite(limit("(x - sin x) / x^3", 0.0, Direction::Both)),
+        1.0 / 6.0
+    ));
+}
+
+#[test]
+fn series() {
+    // needs more derivatives than L'Hôpital's rule is applied
+    let v = finite(limit("(x - sin x)^3 / x^9", 0.0, Direction::Both));
+    assert!(close(v, 1.0 / 216.0));
+}
+
+#[test]
+fn infinite() {
+    assert_eq!(limit("1 / x", 0.0, Direction::Right), Limit::PosInfinity);
+    assert_eq!(limit("1 / x", 0.0, Direction::Left), Limit::NegInfinity);
+    assert_eq!(limit("1 / x", 0.0, Direction::Both), Limit::DoesNotExist);
+    assert_eq!(limit("1 / x^2", 0.0, Direction::Both), Limit::PosInfinity);
+    assert_eq!(limit("ln x", 0.0, Direction::Right), Limit::NegInfinity);
+    assert_eq!(limit("ln x", 0.0, Direction::Left), Limit::DoesNotExist);
+}
+
+#[test]
+fn pole() {
+    assert_eq!(
+        limit("tan x", FRAC_PI_2, Direction::Left),
+        Limit::PosInfinity
+    );
+    assert_eq!(
+        limit("tan x", FRAC_PI_2, Direction::Right),
+        Limit::NegInfinity
+    );
+    assert_eq!(
+        limit("tan x", FRAC_PI_2, Direction::Both),
+        Limit::DoesNotExist
+    );
+}
+
+#[test]
+fn squeezed() {
+    assert_eq!(
+        limit("x * sin (1 / x)", 0.0, Direction::Both),
+        Limit::Finite(0.0)
+    );
+    assert_eq!(limit("sin x", PI, Direction::Both), Limit::Finite(0.0));
+}
+
+#[test]
+fn small() {
+    let v = finite(limit("e^x", -30.0, Direction::Both));
+    assert!((v / (-30f64).exp() - 1.0).abs() < 1e-12, "{v}");
+    assert_eq!(
+        limit("x / 10^13", 1.0, Direction::Both),
+        Limit::Finite(1e-13)
+    );
+    // not 0/0, the numerator is only close to 0
+    assert_eq!(
+        limit("(x + 10^-13) / x", 0.0, Direction::Right),
+        Limit::PosInfinity
+    );
+}
+
+#[test]
+fn at_infinity() {
+    let f = "(2 * x^2 + 1) / (x^2 - 3)";
+    assert!(close(finite(limit(f, f64::INFINITY, Direction::Both)), 2.0));
+    assert!(close(
+        finite(limit(f, f64::NEG_INFINITY, Direction::Both)),
+        2.0
+    ));
+    assert_eq!(
+        limit("e^x / x^2", f64::INFINITY, Direction::Both),
+        Limit::PosInfinity
+    );
+    assert!(close(
+        finite(limit("e^x", f64::NEG_INFINITY, Direction::Both)),
+        0.0
+    ));
+}
+
+#[test]
+fn indeterminate() {
+    assert!(close(finite(limit("x * ln x", 0.0, Direction::Right)), 0.0));
+    assert!(close(finite(limit("x^x", 0.0, Direction::Right)), 1.0));
+    let e = finite(limit("(1 + 1 / x)^x", f64::INFINITY, Direction::Both));
+    assert!((e - std::f64::consts::E).abs() < 1e-6);
+}
+
+#[test]
+fn does_not_exist() {
+    assert_eq!(
+        limit("sin (1 / x)", 0.0, Direction::Right),
+        Limit::DoesNotExist
+    );
+    assert_eq!(
+        limit("sin x", f64::INFINITY, Direction::Both),
+        Limit::DoesNotExist
+    );
+}
@@ -0,0 +1,91 @@
+use super::assert_keeps_value;
+use crate::{prelude::*, Domain};
+
+fn simplify(s: &str, domain: Domain) -> Expr {
+    s.parse::<Expr>().unwrap().simplify_logs(domain)
+}
+
+#[test]
+fn ln_exp() {
+    assert_eq!(simplify("ln (e^(x + 1))", Domain::Real), Var.add(1.0));
+}
+
+#[test]
+fn exp_ln() {
+    assert_eq!(simplify("e^(ln x)", Domain::Real), Expr::E.exp(Var.ln()));
+    assert_eq!(simplify("e^(ln x)", Domain::Positive), Var);
+}
+
+#[test]
+fn log_power() {
+    assert_eq!(simplify("ln (x^3)", Domain::Real), Var.exp(3.0).ln());
+    assert_eq!(
+        simplify("ln (x^3)", Domain::Positive),
+        Num(3.0).mul(Var.ln())
+    );
+    assert_eq!(simplify("log_2 (2^x)", Domain::Real), Var);
+}
+
+#[test]
+fn power_power() {
+    assert_eq!(simplify("(x^0.5)^4", Domain::Real), Var.exp(2.0));
+    assert_eq!(simplify("(x^2)^0.5", Domain::Real), Var.exp(2.0).exp(0.5));
+    assert_eq!(simplify("(x^2)^0.5", Domain::Positive), Var);
+}
+
+#[test]
+fn product() {
+    assert_eq!(simplify("x^2 * x^3 * x", Domain::Real), Var.exp(6.0));
+    assert_eq!(
+        simplify("e^x * e^(2x)", Domain::Real),
+        Expr::E.exp(Var.add(Num(2.0).mul(Var)))
+    );
+    assert_eq!(simplify("x^0.5 * x^0.5", Domain::Positive), Var);
+}
+
+#[test]
+fn change_base() {
+    assert_eq!(
+        simplify("log_x 2", Domain::Real),
+        Num(2f64.ln()).div(Var.ln())
+    );
+}
+
+#[test]
+fn derivative() {
+    // the `ln` of `f^g` is folded back into a power
+    assert_eq!(
+        Expr::E
+            .exp(Var.mul(Var.ln()))
+            .simplify_logs(Domain::Positive),
+        Var.exp(Var)
+    );
+}
+
+#[test]
+fn real_laws_keep_value() {
+    assert_keeps_value(
+        |f| f.simplify_logs(Domain::Real),
+        &[
+            "ln (e^(x^2)) + log_x 5",
+            "ln (3^x)",
+            "(x^2)^3 * x^-4 * x",
+            "2^x * 2^(x + 1)",
+        ],
+        &[-1.3, 0.3, 1.7, 4.0],
+    );
+}
+
+#[test]
+fn positive_laws_keep_value() {
+    assert_keeps_value(
+        |f| f.simplify_logs(Domain::Positive),
+        &[
+            "ln (x^2 * (x + 1)) - ln (x / 2)",
+            "e^(3 * ln x) / x",
+            "(x^1.5)^2 * x^0.5",
+            "log_3 (x^4)",
+        ],
+        &[0.3, 1.7, 4.0],
+    );
+}
@@ -0,0 +1,111 @@
+use super::poly;
+use crate::{prelude::*, NotPolynomial, Polynomial};
+
+#[test]
+fn from_expr() {
+    assert_eq!(poly("(x + 1)^2 - 1"), Polynomial::new([0.0, 2.0, 1.0]));
+    assert_eq!(
+        poly("x^3 / 2 + ln e"),
+        Polynomial::new([1.0, 0.0, 0.0, 0.5])
+    );
+    assert_eq!(poly("x - x"), Polynomial::default());
+    assert_eq!(poly("d/dx x^2"), Polynomial::new([0.0, 2.0]));
+}
+
+#[test]
+fn not_polynomial() {
+    for s in ["sin x", "x^0.5", "1 / x", "x^x", "x^(-1)"] {
+        assert_eq!(
+            Polynomial::try_from(&s.parse::<Expr>().unwrap()),
+            Err(NotPolynomial),
+            "{s}"
+        );
+    }
+}
+
+#[test]
+fn max_degree() {
+    assert_eq!(poly("(x^2 + 1)^500").degree(), Some(1000));
+    assert_eq!(poly("(x + 1)^3").coefs(), [1.0, 3.0, 3.0, 1.0]);
+    for s in ["x^100000", "(x^2 + 1)^501", "x^600 * x^600", "x^(10^300)"] {
+        assert_eq!(
+            Polynomial::try_from(&s.parse::<Expr>().unwrap()),
+            Err(NotPolynomial),
+            "{s}"
+        );
+    }
+    assert_eq!(Var.exp(100000.0).derive_fast(), Var.exp(100000.0).derive());
+}
+
+#[test]
+fn to_expr() {
+    assert_eq!(
+        Expr::from(Polynomial::new([1.0, -2.0, 3.0])).to_string(),
+        "3 * x^2 - 2 * x + 1"
+    );
+    assert_eq!(Expr::from(Polynomial::default()), Num(0.0));
+}
+
+#[test]
+fn degree() {
+    assert_eq!(Polynomial::new([1.0, 2.0, 0.0]).degree(), Some(1));
+    assert_eq!(Polynomial::default().degree(), None);
+}
+
+#[test]
+fn evaluate() {
+    let p = poly("2 * x^3 - x + 5");
+    for x in [-1.5, 0.0, 2.0] {
+        assert_eq!(p.evaluate(x), 2.0 * x * x * x - x + 5.0);
+    }
+}
+
+#[test]
+fn derive() {
+    assert_eq!(
+        poly("x^3 + x^2 + x + 1").derive(),
+        poly("3 * x^2 + 2 * x + 1")
+    );
+    assert_eq!(poly("4").derive(), Polynomial::default());
+}
+
+#[test]
+fn ops() {
+    let a = poly("x + 1");
+    let b = poly("x - 1");
+    assert_eq!(a.clone() + b.clone(), poly("2x"));
+    assert_eq!(a.clone() - b.clone(), poly("2"));
+    assert_eq!(a * b, poly("x^2 - 1"));
+}
+
+#[test]
+fn div_rem() {
+    let (q, r) = poly("x^3 - 2 * x^2 + 4").div_rem(&poly("x - 3"));
+    assert_eq!(q, poly("x^2 + x + 3"));
+    assert_eq!(r, poly("13"));
+
+    let (q, r) = poly("x").div_rem(&poly("x^2"));
+    assert_eq!(q, Polynomial::default());
+    assert_eq!(r, poly("x"));
+}
+
+#[test]
+fn gcd() {
+    let a = poly("(x - 1) * (x + 2) * (x + 3)");
+    let b = poly("(x - 1) * (x + 3) * 2 * (x - 5)");
+    assert_eq!(a.gcd(&b), poly("x^2 + 2 * x - 3"));
+    assert_eq!(poly("x + 1").gcd(&poly("x + 2")), poly("1"));
+}
+
+#[test]
+fn derive_fast() {
+    assert_eq!(
+        "(x^2 + 1) * (x - 3)"
+            .parse::<Expr>()
+            .unwrap()
+            .derive_fast()
+            .to_string(),
+        "3 * x^2 - 6 * x + 1"
+    );
+    assert_eq!(Var.trig(Sin).derive_fast(), Var.trig(Sin).derive());
+}
@@ -0,0 +1,73 @@
+use crate::prelude::*;
+
+#[test]
+fn superscript() {
+    assert_eq!(
+        Var.exp(2.0).mul(Var.trig(Sin)).pretty().to_string(),
+        "x²·sin x"
+    )
+}
+
+#[test]
+fn superscript_expr() {
+    assert_eq!(Expr::E.exp(Var.mul(2.0)).pretty().to_string(), "eˣ·²")
+}
+
+#[test]
+fn raised_exponent() {
+    assert_eq!(
+        Var.exp(Var.add(0.5)).pretty().to_string(),
+        " x + 0.5\n\
+         x"
+    )
+}
+
+#[test]
+fn sqrt() {
+    assert_eq!(Var.add(1.0).sprt().pretty().to_string(), "√(x + 1)")
+}
+
+#[test]
+fn fraction() {
+    assert_eq!(
+        Var.exp(2.0).add(1.0).div(Var.sprt()).pretty().to_string(),
+        " x² + 1\n\
+         ────────\n   \
+            √x"
+    )
+}
+
+#[test]
+fn fraction_beside() {
+    assert_eq!(
+        Num(1.0).add(Var.div(2.0)).pretty().to_string(),
+        "     x\n\
+         1 + ───\n     \
+              2"
+    )
+}
+
+#[test]
+fn fraction_paren() {
+    assert_eq!(
+        Var.div(Var.add(2.0)).exp(2.0).pretty().to_string(),
+        "⎛   x   ⎞²\n\
+         ⎜───────⎟\n\
+         ⎝ x + 2 ⎠"
+    )
+}
+
+#[test]
+fn log_subscript() {
+    assert_eq!(Var.log(2.0).pretty().to_string(), "log₂ x")
+}
+
+#[test]
+fn ln() {
+    assert_eq!(Var.add(1.0).ln().pretty().to_string(), "ln(x + 1)")
+}
+
+#[test]
+fn negative() {
+    assert_eq!(Var.sub(-1.0).pretty().to_string(), "x - (-1)")
+}
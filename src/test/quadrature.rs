@@ -0,0 +1,63 @@
+use std::f64::consts::{E, PI};
+
+use super::parse;
+use crate::prelude::*;
+
+/// The integral of `e^(x^2)` from 0 to 1, which has no elementary antiderivative.
+const GAUSSIAN: f64 = 1.462_651_745_907_181_6;
+
+#[test]
+fn simpson() {
+    let integral = Var.trig(Sin).simpson(0.0, PI, 1e-10).unwrap();
+    assert!((integral.value - 2.0).abs() <= 1e-10);
+    assert!(integral.error <= 1e-10);
+
+    let integral = parse("e^(x^2)").integrate_numeric(0.0, 1.0, 1e-12).unwrap();
+    assert!((integral.value - GAUSSIAN).abs() <= 1e-11);
+}
+
+#[test]
+fn reversed() {
+    let integral = Var.exp(2.0).integrate_numeric(3.0, 0.0, 1e-10).unwrap();
+    assert!((integral.value + 9.0).abs() <= 1e-10);
+}
+
+#[test]
+fn gauss_legendre() {
+    // exact for a polynomial of degree 2n - 1
+    let integral = Var.exp(5.0).gauss_legendre(0.0, 1.0, 3).unwrap();
+    assert!((integral.value - 1.0 / 6.0).abs() <= 1e-15);
+    assert!(integral.error <= 1e-15);
+
+    let integral = parse("e^(x^2)").gauss_legendre(0.0, 1.0, 10).unwrap();
+    assert!((integral.value - GAUSSIAN).abs() <= 1e-14);
+    assert!(integral.error <= 1e-12);
+}
+
+#[test]
+fn romberg() {
+    let integral = Expr::E.exp(Var).romberg(0.0, 1.0, 1e-12).unwrap();
+    assert!((integral.value - (E - 1.0)).abs() <= 1e-12);
+    assert!(integral.error <= 1e-12);
+}
+
+#[test]
+fn pole() {
+    let f = Num(1.0).div(Var);
+    assert_eq!(f.integrate_numeric(-1.0, 1.0, 1e-8), None);
+    assert_eq!(f.romberg(-1.0, 1.0, 1e-8), None);
+}
+
+#[test]
+fn matches_symbolic() {
+    let f = parse("x * e^x");
+    let antiderivative = f.integrate().unwrap();
+    let exact = antiderivative.evaluate(2.0) - antiderivative.evaluate(0.0);
+    for integral in [
+        f.simpson(0.0, 2.0, 1e-10),
+        f.gauss_legendre(0.0, 2.0, 8),
+        f.romberg(0.0, 2.0, 1e-10),
+    ] {
+        assert!((integral.unwrap().value - exact).abs() <= 1e-9);
+    }
+}
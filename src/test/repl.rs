@@ -0,0 +1,146 @@
+use crate::{
+    lang::LangError,
+    prelude::*,
+    repl::{ReplError, Reply, Session, LAST},
+};
+
+#[test]
+fn bind() {
+    let mut session = Session::new();
+    assert_eq!(
+        session.eval("f = x^2"),
+        Ok(Reply::Bound("f".into(), Var.exp(2.0)))
+    );
+    assert_eq!(session.get("f"), Some(&Var.exp(2.0)))
+}
+
+#[test]
+fn prime() {
+    let mut session = Session::new();
+    session.eval("f = x^3").unwrap();
+    assert_eq!(
+        session.eval("f''"),
+        Ok(Reply::Expr(Var.exp(3.0).derive().derive()))
+    );
+    assert_eq!(session.get(LAST), Some(&Var.exp(3.0).derive().derive()))
+}
+
+#[test]
+fn value() {
+    let mut session = Session::new();
+    session.eval("f = x^3").unwrap();
+    assert_eq!(session.eval("f'(2)"), Ok(Reply::Value(12.0)));
+    assert_eq!(
+        session.eval("a = f(1 + 1)"),
+        Ok(Reply::Bound("a".into(), Num(8.0)))
+    )
+}
+
+#[test]
+fn calls() {
+    let mut session = Session::new();
+    session.eval("f = x^2").unwrap();
+    session.eval("g = x + 1").unwrap();
+    assert_eq!(session.eval("f(2) * g(3)"), Ok(Reply::Value(16.0)));
+    assert_eq!(session.eval("f'(2) + 1"), Ok(Reply::Value(5.0)));
+    assert_eq!(session.eval("(f(3))"), Ok(Reply::Value(9.0)))
+}
+
+#[test]
+fn compose() {
+    let mut session = Session::new();
+    session.eval("f = x^2").unwrap();
+    assert_eq!(
+        session.eval("f(x + 1)"),
+        Ok(Reply::Expr(Var.add(1.0).exp(2.0)))
+    );
+    assert_eq!(
+        session.eval("f(x + 1) * 2"),
+        Ok(Reply::Expr(Var.add(1.0).exp(2.0).mul(2.0)))
+    )
+}
+
+#[test]
+fn simplify() {
+    let mut session = Session::new();
+    session.eval("f = x^3").unwrap();
+    assert_eq!(
+        session.eval("simplify f''"),
+        Ok(Reply::Expr(Num(6.0).mul(Var)))
+    )
+}
+
+#[test]
+fn bind_result() {
+    let mut session = Session::new();
+    session.eval("f = x^2").unwrap();
+    session.eval("g = derive f").unwrap();
+    assert_eq!(session.eval("g(3)"), Ok(Reply::Value(6.0)))
+}
+
+#[test]
+fn last() {
+    let mut session = Session::new();
+    session.eval("x^2").unwrap();
+    assert_eq!(
+        session.eval(&format!("{LAST} * 2")),
+        Ok(Reply::Expr(Var.exp(2.0).mul(2.0)))
+    );
+    assert_eq!(
+        session.eval(&format!("{LAST}'")),
+        Ok(Reply::Expr(Var.exp(2.0).mul(2.0).derive()))
+    )
+}
+
+#[test]
+fn history() {
+    let mut session = Session::new();
+    session.eval("f = x").unwrap();
+    session.eval("f'").unwrap();
+    assert_eq!(session.history(), ["f = x", "f'"])
+}
+
+#[test]
+fn unknown() {
+    assert_eq!(
+        Session::new().eval("derive f(x) g(x)"),
+        Ok(Reply::Expr(
+            Var.unknown("f", 0).mul(Var.unknown("g", 0)).derive()
+        ))
+    )
+}
+
+mod error {
+    use super::*;
+
+    #[test]
+    fn unbound() {
+        assert_eq!(
+            Session::new().eval("f'"),
+            Err(ReplError::Unbound("f".into()))
+        )
+    }
+
+    #[test]
+    fn reserved() {
+        assert_eq!(
+            Session::new().eval("sin = x"),
+            Err(ReplError::InvalidName("sin".into()))
+        )
+    }
+
+    #[test]
+    fn unreadable_name() {
+        assert_eq!(
+            Session::new().eval("g2 = x^2"),
+            Err(ReplError::InvalidName("g2".into()))
+        )
+    }
+
+    #[test]
+    fn loc() {
+        let err = Session::new().eval("f = x + )").unwrap_err();
+        assert_eq!(err, ReplError::Lang(LangError::UnexpectedToken(4), 4));
+        assert_eq!(err.loc(), Some(8))
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    lang::LangError,
+    prelude::*,
+    rewrite::{Bindings, Condition, Pattern, Rewriter, Rule},
+};
+
+fn rule(s: &str) -> Rule {
+    s.parse().unwrap()
+}
+
+#[test]
+fn pattern() {
+    assert_eq!(
+        "?a * (?b + 1)".parse::<Pattern>(),
+        Ok(Pattern::Bin(
+            Mul,
+            Box::new(Pattern::Hole("a".into())),
+            Box::new(Pattern::Bin(
+                Add,
+                Box::new(Pattern::Hole("b".into())),
+                Box::new(Pattern::Num(1.0))
+            ))
+        ))
+    )
+}
+
+#[test]
+fn matches() {
+    let pattern: Pattern = "?a + ?a".parse().unwrap();
+    let mut bindings = Bindings::new();
+
+    assert!(pattern.matches(&Var.trig(Sin).add(Var.trig(Sin)), &mut bindings));
+    assert_eq!(bindings["a"], Var.trig(Sin));
+    assert!(!pattern.matches(&Var.add(2.0), &mut Bindings::new()));
+}
+
+#[test]
+fn distribute() {
+    let rewriter = Rewriter::parse("?a * (?b + ?c) -> ?a * ?b + ?a * ?c").unwrap();
+
+    assert_eq!(
+        rewriter.rewrite(Num(2.0).mul(Var.add(Var.add(1.0)))),
+        Num(2.0)
+            .mul(Var)
+            .add(Num(2.0).mul(Var).add(Num(2.0).mul(1.0)))
+    )
+}
+
+#[test]
+fn condition() {
+    let swap = rule("?a * ?b -> ?b * ?a if ?b is num");
+
+    assert_eq!(swap.apply(&Var.mul(2.0)), Some(Num(2.0).mul(Var)));
+    assert_eq!(swap.apply(&Var.mul(Var)), None);
+}
+
+#[test]
+fn conditions() {
+    let cancel = rule("?a / ?b -> 1 if ?a is nonzero and ?b is const");
+
+    assert_eq!(cancel.apply(&Num(2.0).div(2.0)), Some(Num(1.0)));
+    assert_eq!(cancel.apply(&Num(0.0).div(0.0)), None);
+}
+
+#[test]
+fn number_conditions() {
+    let power = rule("?a^?n -> 1 if ?n is int and ?a is positive");
+
+    assert_eq!(power.apply(&Num(2.0).exp(3.0)), Some(Num(1.0)));
+    assert_eq!(power.apply(&Num(2.0).exp(0.5)), None);
+    assert_eq!(power.apply(&Num(-2.0).exp(3.0)), None);
+}
+
+#[test]
+fn custom_condition() {
+    let lhs = "?a ^ ?n".parse().unwrap();
+    let rhs = "?a * ?a".parse().unwrap();
+    let square = Rule::new(lhs, rhs).when(Condition::Custom(std::sync::Arc::new(|b| {
+        b["n"] == Num(2.0)
+    })));
+
+    assert_eq!(square.apply(&Var.exp(2.0)), Some(Var.mul(Var)));
+    assert_eq!(square.apply(&Var.exp(3.0)), None);
+}
+
+#[test]
+fn fixpoint() {
+    let rewriter = Rewriter::parse(
+        "# remove identities
+        ?a * 1 -> ?a
+        ?a + 0 -> ?a",
+    )
+    .unwrap();
+
+    assert_eq!(
+        rewriter.rewrite(Var.mul(1.0).add(0.0).mul(1.0).trig(Sin)),
+        Var.trig(Sin)
+    )
+}
+
+#[test]
+fn endless() {
+    let rewriter = Rewriter::parse("?a + ?b -> ?b + ?a").unwrap();
+
+    assert_eq!(rewriter.rewrite(Var.add(1.0)), Var.add(1.0))
+}
+
+#[test]
+fn simplify() {
+    let rewriter = Rewriter::parse("?a * ?a -> ?a^2").unwrap();
+
+    // `x * (2 - 1)` only matches once the constant is folded
+    assert_eq!(
+        rewriter.rewrite_simplify(Var.mul(Num(2.0).sub(1.0)).mul(Var)),
+        Var.exp(2.0)
+    )
+}
+
+#[test]
+fn order() {
+    let rewriter = Rewriter::parse("sin ?a -> ?a\nsin x -> 0").unwrap();
+
+    assert_eq!(rewriter.rewrite(Var.trig(Sin)), Var)
+}
+
+mod error {
+    use super::*;
+
+    #[test]
+    fn arrow() {
+        assert_eq!(
+            "?a + 1".parse::<Rule>().unwrap_err(),
+            LangError::UnexpectedToken(usize::MAX)
+        )
+    }
+
+    #[test]
+    fn unbound_hole() {
+        assert_eq!(
+            "?a -> ?a + ?b".parse::<Rule>().unwrap_err(),
+            LangError::InvalidWord("?b".into(), 11)
+        )
+    }
+
+    #[test]
+    fn condition() {
+        assert_eq!(
+            "?a -> 1 if ?a is big".parse::<Rule>().unwrap_err(),
+            LangError::InvalidWord("big".into(), 17)
+        )
+    }
+
+    #[test]
+    fn loc() {
+        assert_eq!(
+            Rewriter::parse("?a -> ?a\n?a -> )").unwrap_err(),
+            LangError::UnexpectedToken(15)
+        )
+    }
+
+    #[test]
+    fn hole_in_expr() {
+        assert_eq!("?a + 1".parse::<Expr>(), Err(LangError::UnexpectedToken(0)))
+    }
+}
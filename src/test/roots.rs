@@ -0,0 +1,179 @@
+use std::f64::consts::PI;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use super::poly;
+use crate::{prelude::*, Complex, Function, Polynomial, Root};
+
+/// The places of the roots, without their errors.
+fn xs(roots: &[Root]) -> Vec<f64> {
+    roots.iter().map(|r| r.x).collect()
+}
+
+fn assert_close(a: &[f64], b: &[f64], eps: f64) {
+    assert_eq!(a.len(), b.len(), "{a:?} != {b:?}");
+    for (x, y) in a.iter().zip(b) {
+        assert!((x - y).abs() <= eps, "{a:?} != {b:?}");
+    }
+}
+
+mod polynomial {
+    use super::*;
+
+    #[test]
+    fn linear() {
+        assert_eq!(xs(&poly("2 * x - 3").real_roots()), [1.5]);
+    }
+
+    #[test]
+    fn quadratic() {
+        assert_eq!(xs(&poly("x^2 - 5 * x + 6").real_roots()), [2.0, 3.0]);
+        assert_eq!(
+            poly("x^2 + 2 * x + 5").complex_roots(),
+            [Complex::new(-1.0, -2.0), Complex::new(-1.0, 2.0)]
+        );
+        assert!(poly("x^2 + 1").real_roots().is_empty());
+    }
+
+    #[test]
+    fn quadratic_cancellation() {
+        // the small root is lost with the textbook formula
+        let roots = Polynomial::new([1.0, -1e8, 1.0]).real_roots();
+        assert!((roots[0].x - 1e-8).abs() <= 1e-22);
+    }
+
+    #[test]
+    fn durand_kerner() {
+        let p = poly("(x - 1) * (x - 2) * (x - 3) * (x + 4) * (x^2 + 1)");
+        assert_close(&xs(&p.real_roots()), &[-4.0, 1.0, 2.0, 3.0], 1e-12);
+
+        let roots = p.complex_roots();
+        assert_eq!(roots.len(), 6);
+        for z in roots {
+            let value = p
+                .coefs()
+                .iter()
+                .rev()
+                .fold(Complex::new(0.0, 0.0), |acc, &c| {
+                    acc * z + Complex::new(c, 0.0)
+                });
+            assert!(value.norm() < 1e-9, "{z:?}");
+        }
+    }
+
+    #[test]
+    fn double_root() {
+        assert_close(
+            &xs(&poly("(x - 2)^2 * (x + 1)").real_roots()),
+            &[-1.0, 2.0, 2.0],
+            1e-6,
+        );
+    }
+
+    #[test]
+    fn error() {
+        let p = poly("(x - 1) * (x - 2)^2 * (x - 3) * (x^2 + 1)");
+        let roots = p.real_roots();
+        assert_eq!(roots.len(), 4);
+        for (root, exact) in roots.iter().zip([1.0, 2.0, 2.0, 3.0]) {
+            assert!((root.x - exact).abs() <= root.error, "{root:?}");
+        }
+        // a double root is only known to about the square root of the precision
+        assert!(
+            roots[0].error < 1e-10 && roots[3].error < 1e-10,
+            "{roots:?}"
+        );
+    }
+
+    #[test]
+    fn constant() {
+        assert!(poly("3").real_roots().is_empty());
+        assert!(Polynomial::default().complex_roots().is_empty());
+    }
+}
+
+mod expr {
+    use super::*;
+
+    #[test]
+    fn newton() {
+        let root = "cos x - x".parse::<Expr>().unwrap().newton(1.0).unwrap();
+        assert!((root - 0.739_085_133_215_160_6).abs() < 1e-15);
+        assert_eq!("x^2 + 1".parse::<Expr>().unwrap().newton(0.0), None);
+    }
+
+    #[test]
+    fn bisection() {
+        let root = Var.exp(2.0).sub(2.0).bisection(0.0, 2.0).unwrap();
+        assert!((root.x - 2f64.sqrt()).abs() <= root.error);
+        assert_eq!(Var.exp(2.0).bisection(-1.0, 1.0), None);
+    }
+
+    #[test]
+    fn brent() {
+        let root = Var.exp(3.0).sub(Var).sub(2.0).brent(1.0, 2.0).unwrap();
+        assert!((root.x - 1.521_379_706_804_567_6).abs() <= root.error.max(1e-15));
+        assert!(root.error < 1e-12);
+    }
+
+    #[test]
+    fn brent_zero() {
+        let root = Var.trig(Sin).brent(-1.0, 2.0).unwrap();
+        assert!(root.x.abs() <= 1e-12 && root.error <= 1e-12, "{root:?}");
+
+        // a triple root converges slowly, it has to stop at the absolute tolerance
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counted = calls.clone();
+        let cube = Function::new(
+            "counted_cube",
+            move |x| {
+                counted.fetch_add(1, Ordering::Relaxed);
+                x.powi(3)
+            },
+            |_| Num(3.0).mul(Var.exp(2.0)),
+        );
+        let root = Var.func(&cube).brent(-1.0, 2.0).unwrap();
+        assert!(root.x.abs() <= 1e-12 && root.error <= 1e-12, "{root:?}");
+        assert!(calls.load(Ordering::Relaxed) < 200, "{calls:?}");
+    }
+
+    #[test]
+    fn bisection_infinite() {
+        assert_eq!(Var.sub(1.0).bisection(0.0, f64::INFINITY), None);
+        let root = Var.sub(1.0).bisection(-f64::MAX, f64::MAX).unwrap();
+        assert!((root.x - 1.0).abs() <= root.error.max(1e-15), "{root:?}");
+    }
+
+    #[test]
+    fn pole() {
+        assert_eq!(Num(1.0).div(Var).brent(-1.0, 1.0), None);
+        assert_eq!(xs(&Var.trig(Tan).roots(0.0..=3.0)), [0.0]);
+        assert!(Num(1.0).div(Var).roots(-1.0..=1.0).is_empty());
+        assert_close(&xs(&Var.trig(Tan).roots(1.0..=4.0)), &[PI], 1e-12);
+    }
+
+    #[test]
+    fn roots() {
+        let roots = Var.trig(Sin).roots(-1.0..=7.0);
+        let xs: Vec<_> = roots.iter().map(|r| r.x).collect();
+        assert_close(&xs, &[0.0, PI, 2.0 * PI], 1e-12);
+        for r in roots {
+            assert!(r.error < 1e-9);
+        }
+    }
+
+    #[test]
+    fn tangent_root() {
+        let roots = "(x - 1)^2 * (x + 2)"
+            .parse::<Expr>()
+            .unwrap()
+            .roots(-3.0..=3.0);
+        let xs: Vec<_> = roots.iter().map(|r| r.x).collect();
+        assert_close(&xs, &[-2.0, 1.0], 1e-9);
+    }
+
+    #[test]
+    fn no_root() {
+        assert!(Var.exp(2.0).add(1.0).roots(-5.0..=5.0).is_empty());
+    }
+}
@@ -0,0 +1,49 @@
+use crate::prelude::*;
+
+#[test]
+fn fold() {
+    assert_eq!(Num(2.0).mul(3.0).add(1.0).simplify(), Num(7.0))
+}
+
+#[test]
+fn identity() {
+    assert_eq!(
+        Var.mul(1.0)
+            .add(0.0)
+            .exp(1.0)
+            .sub(Num(0.0).mul(Var))
+            .simplify(),
+        Var
+    )
+}
+
+#[test]
+fn constant_left() {
+    assert_eq!(
+        Var.mul(2.0).mul(Num(3.0).mul(Var.trig(Sin))).simplify(),
+        Num(6.0).mul(Var.mul(Var.trig(Sin)))
+    )
+}
+
+#[test]
+fn negative() {
+    assert_eq!(Var.add(-2.0).simplify(), Var.sub(2.0))
+}
+
+#[test]
+fn derivative() {
+    assert_eq!(Var.exp(3.0).derive().derive().simplify(), Num(6.0).mul(Var))
+}
+
+#[test]
+fn chain() {
+    assert_eq!(Var.trig(Sin).derive().simplify(), Var.trig(Cos))
+}
+
+#[test]
+fn nan() {
+    // ln(-2) folds to NaN, which is never equal to itself
+    let expr = Num(-2.0).exp(Var).derive().simplify();
+    assert!(expr.evaluate(1.0).is_nan());
+    assert!(Num(-2.0).ln().simplify().evaluate(0.0).is_nan());
+}
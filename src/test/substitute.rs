@@ -0,0 +1,74 @@
+use crate::prelude::*;
+
+#[test]
+fn substitute() {
+    assert_eq!(
+        Var.exp(2.0).add(Var).substitute(&Var, &Num(3.0)),
+        Num(3.0).exp(2.0).add(3.0)
+    )
+}
+
+#[test]
+fn substitute_expr() {
+    assert_eq!(
+        Var.trig(Sin)
+            .exp(2.0)
+            .substitute(&Var.trig(Sin), &Var.trig(Cos)),
+        Var.trig(Cos).exp(2.0)
+    )
+}
+
+#[test]
+fn compose() {
+    assert_eq!(
+        Var.ln().compose(Var.exp(2.0).add(1.0)),
+        Var.exp(2.0).add(1.0).ln()
+    )
+}
+
+#[test]
+fn compose_derivative() {
+    assert_eq!(
+        Var.exp(3.0).derivative(1).compose(Var.trig(Sin)),
+        Var.exp(3.0).derive().compose(Var.trig(Sin))
+    )
+}
+
+/// `(f∘g)' == f'(g)·g'`, checked numerically for every pair of functions.
+#[test]
+fn chain_rule() {
+    let funcs = [
+        Var.exp(3.0),
+        Var.trig(Sin),
+        Var.trig(Tan),
+        Var.trig(Sec),
+        Var.ln(),
+        Expr::E.exp(Var),
+        Num(2.0).exp(Var),
+        Var.log(3.0),
+        Var.sprt(),
+        Var.exp(2.0).add(Var.mul(3.0)),
+        Var.div(Var.add(1.0)),
+    ];
+
+    for f in &funcs {
+        for g in &funcs {
+            let composed = f.clone().compose(g.clone()).derive();
+            let chained = f
+                .clone()
+                .derive()
+                .compose(g.clone())
+                .mul(g.clone().derive());
+
+            for x in [0.3, 0.7, 1.1, 2.5] {
+                let (a, b) = (composed.evaluate(x), chained.evaluate(x));
+                if a.is_finite() || b.is_finite() {
+                    assert!(
+                        (a - b).abs() <= 1e-9 * a.abs().max(1.0),
+                        "({f}) o ({g}) at {x}: {a} != {b}"
+                    );
+                }
+            }
+        }
+    }
+}
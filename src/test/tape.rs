@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use crate::{lang::LangError, prelude::*, Tape};
+
+use super::display::Rng;
+
+fn a() -> Expr {
+    Expr::param("a").unwrap()
+}
+
+fn b() -> Expr {
+    Expr::param("b").unwrap()
+}
+
+#[test]
+fn param() {
+    let f = a().mul(Var.exp(2.0)).add(b());
+    assert_eq!(f.to_string(), "$a * x^2 + $b");
+    assert_eq!(f.clone().derive().simplify().to_string(), "2 * (x * $a)");
+    assert!(f.evaluate(1.0).is_nan());
+    assert_eq!(
+        f.substitute(&a(), &Num(3.0))
+            .substitute(&b(), &Num(1.0))
+            .evaluate(2.0),
+        13.0
+    );
+}
+
+#[test]
+fn parse_param() {
+    assert_eq!("$a * x^2 + $b".parse(), Ok(a().mul(Var.exp(2.0)).add(b())));
+    assert_eq!(
+        "2 * $x".parse::<Expr>(),
+        Err(LangError::InvalidWord("x".into(), 4))
+    );
+}
+
+#[test]
+fn param_name() {
+    for name in ["", "x", "e", "sin", "dx", "a b", "a1"] {
+        assert_eq!(
+            Expr::param(name),
+            Err(LangError::InvalidWord(name.into(), 0)),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn gradient() {
+    // a x^2 + b x
+    let tape = Tape::new(&a().mul(Var.exp(2.0)).add(b().mul(Var)));
+    assert_eq!(tape.variables(), ["a", "x", "b"]);
+
+    let (value, gradient) = tape.gradient(&[3.0, 2.0, 5.0]);
+    assert_eq!(value, 22.0);
+    assert_eq!(gradient, [4.0, 17.0, 2.0]);
+    assert_eq!(tape.evaluate(&[3.0, 2.0, 5.0]), 22.0);
+}
+
+#[test]
+fn shared_variable() {
+    // sin(a b) + a, the partial of a adds both uses
+    let tape = Tape::new(&a().mul(b()).trig(Sin).add(a()));
+    let (_, gradient) = tape.gradient(&[2.0, 0.5]);
+    assert!((gradient[0] - (0.5 * 1f64.cos() + 1.0)).abs() < 1e-15);
+    assert!((gradient[1] - 2.0 * 1f64.cos()).abs() < 1e-15);
+}
+
+#[test]
+fn constant() {
+    // (-2)^2 does not depend on x, so the log of its base is never taken
+    let tape = Tape::new(&Num(-2.0).exp(2.0).mul(Var));
+    assert_eq!(tape.gradient(&[3.0]), (12.0, vec![4.0]));
+}
+
+#[test]
+fn expr_gradient() {
+    let f = a().mul(Expr::E.exp(b().mul(Var)));
+    let point = HashMap::from([("a".into(), 2.0), ("b".into(), 0.0), ("x".into(), 3.0)]);
+    let gradient = f.gradient(&point);
+    assert_eq!(gradient["a"], 1.0);
+    assert_eq!(gradient["b"], 6.0);
+    assert_eq!(gradient["x"], 0.0);
+
+    let gradient = f.gradient(&HashMap::new());
+    assert!(gradient["a"].is_nan());
+}
+
+#[test]
+#[should_panic(expected = "one value is needed per variable")]
+fn missing_value() {
+    Tape::new(&a().add(Var)).gradient(&[1.0]);
+}
+
+#[test]
+fn matches_dual() {
+    let mut rng = Rng(0xd1b5_4a32_d192_ed03);
+
+    let mut compared = 0;
+    for _ in 0..2000 {
+        let expr = rng.expr(4);
+        let tape = Tape::new(&expr);
+        for x in [-1.7, 0.3, 2.2] {
+            let dual = expr.evaluate_dual(x);
+            let values = if tape.variables().is_empty() {
+                vec![]
+            } else {
+                vec![x]
+            };
+            let (value, gradient) = tape.gradient(&values);
+            let derivative = gradient.first().copied().unwrap_or(0.0);
+
+            assert_eq!(value.to_bits(), dual.value.to_bits(), "{expr}");
+            if derivative.is_finite() && dual.derivative.is_finite() {
+                compared += 1;
+                assert!(
+                    (derivative - dual.derivative).abs() <= 1e-9 * (1.0 + dual.derivative.abs()),
+                    "d/dx {expr} at {x}: {derivative} != {}",
+                    dual.derivative
+                );
+            }
+        }
+    }
+    assert!(compared > 1000);
+}
+
+#[test]
+fn matches_substitution() {
+    // the partial of a param is the derivative with it as x and the others fixed
+    let f = a()
+        .mul(b())
+        .trig(Sin)
+        .add(b().exp(2.0).mul(Var.ln()))
+        .div(a().add(Var));
+    let tape = Tape::new(&f);
+    let point = [("a", 0.7), ("b", -1.3), ("x", 2.1)];
+    let values: Vec<_> = tape
+        .variables()
+        .iter()
+        .map(|v| point.iter().find(|(name, _)| name == v).unwrap().1)
+        .collect();
+    let (_, gradient) = tape.gradient(&values);
+
+    for (i, name) in tape.variables().iter().enumerate() {
+        // `x` is renamed to `$t` while the param is x
+        let mut g = f.clone();
+        if name != "x" {
+            g = g
+                .substitute(&Var, &Expr::param("t").unwrap())
+                .substitute(&Expr::param(name.as_str()).unwrap(), &Var);
+        }
+        for (other, value) in point {
+            if other != name {
+                let other = if other == "x" { "t" } else { other };
+                g = g.substitute(&Expr::param(other).unwrap(), &Num(value));
+            }
+        }
+        let (_, value) = point.iter().find(|(n, _)| n == name).unwrap();
+        let expected = g.evaluate_dual(*value).derivative;
+        assert!((gradient[i] - expected).abs() < 1e-12, "{name}");
+    }
+}
@@ -0,0 +1,48 @@
+use crate::prelude::*;
+
+#[test]
+fn polynomial() {
+    assert_eq!(
+        Var.exp(2.0).taylor(1.0, 4).to_string(),
+        "1 + 2 * (x - 1) + (x - 1)^2"
+    );
+    assert_eq!(
+        Var.exp(3.0).taylor(-2.0, 1).to_string(),
+        "-8 + 12 * (x + 2)"
+    );
+}
+
+#[test]
+fn maclaurin() {
+    assert_eq!(
+        Var.trig(Sin).maclaurin(5),
+        Var.sub(Num(1.0 / 6.0).mul(Var.exp(3.0)))
+            .add(Num(1.0 / 120.0).mul(Var.exp(5.0)))
+    );
+    assert_eq!(
+        Expr::E.exp(Var).maclaurin(2),
+        Num(1.0).add(Var).add(Num(0.5).mul(Var.exp(2.0)))
+    );
+}
+
+#[test]
+fn zero() {
+    assert_eq!(Var.trig(Sin).taylor(0.0, 0), Num(0.0));
+}
+
+#[test]
+fn approximation() {
+    let f = Var.trig(Cos);
+    let series = f.taylor(0.5, 6);
+    for x in [0.0, 0.4, 1.0] {
+        let error = (f.evaluate(x) - series.evaluate(x)).abs();
+        assert!(error <= f.taylor_remainder(0.5, 6, x) + 1e-15, "{x}");
+    }
+}
+
+#[test]
+fn remainder() {
+    // every derivative of e^x is e^x, the max on [0, 1] is at 1
+    let bound = Expr::E.exp(Var).taylor_remainder(0.0, 3, 1.0);
+    assert!((bound - std::f64::consts::E / 24.0).abs() < 1e-12);
+}
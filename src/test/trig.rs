@@ -0,0 +1,65 @@
+use super::assert_keeps_value;
+use crate::prelude::*;
+
+fn simplify(s: &str) -> Expr {
+    s.parse::<Expr>().unwrap().simplify_trig()
+}
+
+#[test]
+fn to_sin_cos() {
+    assert_eq!(
+        Var.trig(Tan).add(Var.trig(Sec)).to_sin_cos(),
+        Var.trig(Sin)
+            .div(Var.trig(Cos))
+            .add(Num(1.0).div(Var.trig(Cos)))
+    )
+}
+
+#[test]
+fn pythagorean() {
+    assert_eq!(simplify("(sin x)^2 + (cos x)^2"), Num(1.0));
+    assert_eq!(simplify("(cos (2x))^2 + (sin (2x))^2"), Num(1.0));
+    assert_eq!(simplify("1 - (sin x)^2"), Var.trig(Cos).exp(2.0));
+}
+
+#[test]
+fn double_angle() {
+    assert_eq!(simplify("2 sin x * cos x"), Num(2.0).mul(Var).trig(Sin));
+    assert_eq!(
+        simplify("(cos x)^2 - (sin x)^2"),
+        Num(2.0).mul(Var).trig(Cos)
+    );
+}
+
+#[test]
+fn reciprocal() {
+    assert_eq!(simplify("sin x * csc x"), Num(1.0));
+    assert_eq!(simplify("tan x * cot x"), Num(1.0));
+}
+
+#[test]
+fn derivatives() {
+    assert_eq!(
+        Var.trig(Tan).derive().simplify_trig(),
+        Num(1.0).div(Var.trig(Cos).exp(2.0))
+    );
+    assert_eq!(
+        Var.trig(Sec).derive().simplify_trig(),
+        Var.trig(Sin).div(Var.trig(Cos).exp(2.0))
+    );
+}
+
+#[test]
+fn identities_keep_value() {
+    assert_keeps_value(
+        Expr::simplify_trig,
+        &[
+            "(sec x)^2 - (tan x)^2",
+            "1 - (cos (x^2))^2",
+            "2 * cos x * sin x / tan x",
+            "(cos x)^2 - (sin x)^2 + csc x * cot x",
+            "(1 / sin x)^3 * sin x",
+        ],
+        &[0.3, 1.1, 2.5],
+    );
+}
@@ -0,0 +1,55 @@
+//! Run the `stoat` binary and check what it prints and its exit code.
+
+use std::process::{Command, Output};
+
+fn stoat(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_stoat"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into()
+}
+
+#[test]
+fn derive() {
+    let output = stoat(&["-s", "x^3"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "3 * x^2\n");
+}
+
+#[test]
+fn simplify_without_deriving() {
+    let output = stoat(&["-n", "0", "-s", "x + 0"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "x\n");
+}
+
+#[test]
+fn eval() {
+    let output = stoat(&["-s", "-e", "2", "x^2"]);
+    assert_eq!(stdout(&output), "2 * x\nat x = 2: 4\n");
+}
+
+#[test]
+fn help() {
+    let output = stoat(&["--help"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Derive an expression of x."));
+}
+
+#[test]
+fn lang_error() {
+    let output = stoat(&["x + )"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("1 | x + )"));
+}
+
+#[test]
+fn usage_error() {
+    assert_eq!(stoat(&["--fast", "x"]).status.code(), Some(2));
+    assert_eq!(stoat(&["-f", "/does/not/exist"]).status.code(), Some(2));
+}